@@ -36,6 +36,8 @@ pub struct MarketCreatedEvent {
     pub resolution_time_ns: String,
     pub creator: String,
     pub initial_liquidity: String,
+    #[serde(default)]
+    pub fee_bps: Option<u16>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +50,13 @@ pub struct TradeEvent {
     pub token_amount: String,
     pub yes_price: String,
     pub no_price: String,
+    // Pool state after the trade (event standard >= 1.1.0)
+    #[serde(default)]
+    pub fee_amount: Option<String>,
+    #[serde(default)]
+    pub yes_reserve: Option<String>,
+    #[serde(default)]
+    pub no_reserve: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +89,23 @@ pub struct MarketDisputedEvent {
     pub assertion_id: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionSubmissionFailedEvent {
+    pub market_id: u64,
+    pub assertion_id: String,
+    pub restored_status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionRejectedEvent {
+    pub market_id: u64,
+    pub assertion_id: String,
+    pub rejected_outcome: Option<Outcome>,
+    // Closed for re-resolution, or Open again after a rejected early assertion
+    #[serde(default)]
+    pub restored_status: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketSettledEvent {
     pub market_id: u64,
@@ -207,8 +233,9 @@ pub fn ns_string_to_ms(ns: &str) -> i64 {
         .unwrap_or(0)
 }
 
+/// Contract price (scaled by `PRICE_SCALE`) as a percentage.
 pub fn price_raw_to_float(raw: &str) -> f64 {
     raw.parse::<u128>()
-        .map(|v| (v as f64) / 10_000.0)
+        .map(|v| (v as f64) * 100.0 / market_math::PRICE_SCALE as f64)
         .unwrap_or(0.0)
 }
@@ -11,9 +11,10 @@ use crate::db::{
     DbPool, EventInsert, LifecycleProjectionUpdate, ProjectionUpdate,
 };
 use crate::types::{
-    ns_string_to_ms, price_raw_to_float, LiquidityAddedEvent, LiquidityRemovedEvent,
-    LiveTradeEvent, LiveWsMessage, LogNep297Event, MarketCreatedEvent, MarketDisputedEvent,
-    MarketSettledEvent, RedeemedEvent, ResolutionSubmittedEvent, TradeEvent,
+    ns_string_to_ms, price_raw_to_float, AssertionRejectedEvent, LiquidityAddedEvent,
+    LiquidityRemovedEvent, LiveTradeEvent, LiveWsMessage, LogNep297Event, MarketCreatedEvent,
+    MarketDisputedEvent, MarketSettledEvent, RedeemedEvent, ResolutionSubmissionFailedEvent,
+    ResolutionSubmittedEvent, TradeEvent,
 };
 
 const DEFAULT_ORACLE_LIVENESS_NS: u64 = 2 * 60 * 60 * 1_000_000_000;
@@ -287,6 +288,41 @@ async fn handle_event(
                 .await?;
             }
         }
+        "resolution_submission_failed" => {
+            let payload: ResolutionSubmissionFailedEvent =
+                serde_json::from_value(event_data_inner.clone())?;
+            process_generic_event(
+                pool,
+                &event,
+                payload.market_id,
+                &payload.restored_status.to_lowercase(),
+                None,
+                None,
+                None,
+                &event_json,
+            )
+            .await?;
+        }
+        "assertion_rejected" => {
+            let payload: AssertionRejectedEvent =
+                serde_json::from_value(event_data_inner.clone())?;
+            let status = payload
+                .restored_status
+                .as_deref()
+                .unwrap_or("Closed")
+                .to_lowercase();
+            process_generic_event(
+                pool,
+                &event,
+                payload.market_id,
+                &status,
+                None,
+                None,
+                None,
+                &event_json,
+            )
+            .await?;
+        }
         "market_settled" => {
             let payload: MarketSettledEvent = serde_json::from_value(event_data_inner.clone())?;
             let inserted = process_generic_event(
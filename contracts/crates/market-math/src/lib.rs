@@ -0,0 +1,417 @@
+//! Pure AMM math shared by the market contract, the indexer and clients.
+//!
+//! Everything here is `no_std`, allocation-free and checked: products are taken
+//! in 256 bits, every subtraction that could underflow returns an error, and each
+//! division states its rounding direction. Rounding always favours the pool.
+
+#![no_std]
+
+use core::fmt;
+
+mod u256 {
+    #![allow(clippy::manual_div_ceil, clippy::assign_op_pattern, clippy::ptr_offset_with_cast)]
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+use u256::U256;
+
+/// Prices are fixed-point with 6 decimals (1_000_000 = 1.0).
+pub const PRICE_SCALE: u128 = 1_000_000;
+
+/// Fee basis-point denominator (10_000 = 100%).
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// A result does not fit in `u128`.
+    Overflow,
+    DivisionByZero,
+    /// The pool cannot pay out the requested amount.
+    InsufficientReserves,
+    /// A fee above [`BPS_DENOMINATOR`] basis points.
+    InvalidFee,
+}
+
+impl MathError {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MathError::Overflow => "Math overflow",
+            MathError::DivisionByZero => "Division by zero",
+            MathError::InsufficientReserves => "Insufficient reserves",
+            MathError::InvalidFee => "Fee exceeds 100%",
+        }
+    }
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+pub type MathResult<T> = Result<T, MathError>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Outcome side of a trade.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Yes,
+    No,
+}
+
+/// `a * b / denominator` with a 256-bit intermediate product.
+pub fn mul_div(a: u128, b: u128, denominator: u128, rounding: Rounding) -> MathResult<u128> {
+    if denominator == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    let (quotient, remainder) = (U256::from(a) * U256::from(b)).div_mod(U256::from(denominator));
+    let quotient = if rounding == Rounding::Up && !remainder.is_zero() {
+        quotient + 1
+    } else {
+        quotient
+    };
+    if quotient > U256::from(u128::MAX) {
+        return Err(MathError::Overflow);
+    }
+    Ok(quotient.as_u128())
+}
+
+fn add(a: u128, b: u128) -> MathResult<u128> {
+    a.checked_add(b).ok_or(MathError::Overflow)
+}
+
+fn sub(a: u128, b: u128) -> MathResult<u128> {
+    a.checked_sub(b).ok_or(MathError::InsufficientReserves)
+}
+
+/// Fee charged on `amount`, rounded down. Never more than `amount`.
+pub fn fee_amount(amount: u128, fee_bps: u16) -> MathResult<u128> {
+    if fee_bps as u128 > BPS_DENOMINATOR {
+        return Err(MathError::InvalidFee);
+    }
+    mul_div(amount, fee_bps as u128, BPS_DENOMINATOR, Rounding::Down)
+}
+
+/// Spot (YES, NO) prices scaled by [`PRICE_SCALE`]; 50/50 for an empty pool.
+pub fn spot_prices(yes_reserve: u128, no_reserve: u128) -> (u128, u128) {
+    if yes_reserve == 0 || no_reserve == 0 {
+        return (PRICE_SCALE / 2, PRICE_SCALE / 2);
+    }
+    let total = U256::from(yes_reserve) + U256::from(no_reserve);
+    let scale = U256::from(PRICE_SCALE);
+    // Both quotients are at most PRICE_SCALE
+    (
+        (U256::from(no_reserve) * scale / total).as_u128(),
+        (U256::from(yes_reserve) * scale / total).as_u128(),
+    )
+}
+
+/// Relative price move from `before` to `after` in basis points, rounded up.
+pub fn price_impact_bps(before: u128, after: u128) -> MathResult<u128> {
+    mul_div(before.abs_diff(after), BPS_DENOMINATOR, before, Rounding::Up)
+}
+
+// ── Trades ─────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BuyQuote {
+    pub fee: u128,
+    pub net_collateral: u128,
+    pub tokens_out: u128,
+    pub yes_reserve: u128,
+    pub no_reserve: u128,
+}
+
+/// Buy `side` with `collateral`: the fee is taken, the net amount is minted as
+/// YES+NO into both reserves, and the opposite side is swapped in again at the
+/// constant product. The remaining reserve is rounded up.
+pub fn quote_buy(
+    yes_reserve: u128,
+    no_reserve: u128,
+    collateral: u128,
+    fee_bps: u16,
+    side: Side,
+) -> MathResult<BuyQuote> {
+    let fee = fee_amount(collateral, fee_bps)?;
+    let net_collateral = sub(collateral, fee)?;
+
+    let yes_r = add(yes_reserve, net_collateral)?;
+    let no_r = add(no_reserve, net_collateral)?;
+
+    let (tokens_out, yes_reserve, no_reserve) = match side {
+        Side::Yes => {
+            let final_no = add(no_r, net_collateral)?;
+            let final_yes = mul_div(yes_r, no_r, final_no, Rounding::Up)?;
+            (sub(yes_r, final_yes)?, final_yes, final_no)
+        }
+        Side::No => {
+            let final_yes = add(yes_r, net_collateral)?;
+            let final_no = mul_div(yes_r, no_r, final_yes, Rounding::Up)?;
+            (sub(no_r, final_no)?, final_yes, final_no)
+        }
+    };
+
+    Ok(BuyQuote {
+        fee,
+        net_collateral,
+        tokens_out,
+        yes_reserve,
+        no_reserve,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SellQuote {
+    /// Matched YES+NO pairs burned to release collateral.
+    pub collateral_before_fee: u128,
+    pub fee: u128,
+    pub collateral_out: u128,
+    pub yes_reserve: u128,
+    pub no_reserve: u128,
+}
+
+/// Sell `tokens_in` of `side`: the tokens enter their reserve, opposite tokens are
+/// extracted at the constant product (rounded up, in the pool's favour), and matched
+/// pairs are burned for collateral before the fee.
+pub fn quote_sell(
+    yes_reserve: u128,
+    no_reserve: u128,
+    tokens_in: u128,
+    fee_bps: u16,
+    side: Side,
+) -> MathResult<SellQuote> {
+    let (collateral_before_fee, yes_reserve, no_reserve) = match side {
+        Side::Yes => {
+            let new_yes = add(yes_reserve, tokens_in)?;
+            let new_no = mul_div(yes_reserve, no_reserve, new_yes, Rounding::Up)?;
+            let pairs = tokens_in.min(sub(no_reserve, new_no)?);
+            (pairs, sub(new_yes, pairs)?, new_no)
+        }
+        Side::No => {
+            let new_no = add(no_reserve, tokens_in)?;
+            let new_yes = mul_div(yes_reserve, no_reserve, new_no, Rounding::Up)?;
+            let pairs = tokens_in.min(sub(yes_reserve, new_yes)?);
+            (pairs, new_yes, sub(new_no, pairs)?)
+        }
+    };
+
+    let fee = fee_amount(collateral_before_fee, fee_bps)?;
+    Ok(SellQuote {
+        collateral_before_fee,
+        fee,
+        collateral_out: sub(collateral_before_fee, fee)?,
+        yes_reserve,
+        no_reserve,
+    })
+}
+
+// ── Liquidity ──────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidityDeposit {
+    pub lp_shares: u128,
+    pub yes_add: u128,
+    pub no_add: u128,
+}
+
+/// Shares and reserve additions for depositing `amount` into an existing pool,
+/// all proportional to `total_collateral` and rounded down.
+pub fn quote_add_liquidity(
+    amount: u128,
+    yes_reserve: u128,
+    no_reserve: u128,
+    total_collateral: u128,
+    total_lp_shares: u128,
+) -> MathResult<LiquidityDeposit> {
+    let lp_shares = if total_lp_shares == 0 {
+        amount
+    } else {
+        mul_div(amount, total_lp_shares, total_collateral, Rounding::Down)?
+    };
+    Ok(LiquidityDeposit {
+        lp_shares,
+        yes_add: mul_div(amount, yes_reserve, total_collateral, Rounding::Down)?,
+        no_add: mul_div(amount, no_reserve, total_collateral, Rounding::Down)?,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidityWithdrawal {
+    pub collateral_out: u128,
+    pub fees_out: u128,
+    pub yes_remove: u128,
+    pub no_remove: u128,
+}
+
+/// Pro-rata share of collateral, LP fees and reserves for burning `shares`, rounded down.
+pub fn quote_remove_liquidity(
+    shares: u128,
+    total_lp_shares: u128,
+    total_collateral: u128,
+    accrued_fees: u128,
+    yes_reserve: u128,
+    no_reserve: u128,
+) -> MathResult<LiquidityWithdrawal> {
+    if shares > total_lp_shares {
+        return Err(MathError::InsufficientReserves);
+    }
+    let pro_rata = |value| mul_div(shares, value, total_lp_shares, Rounding::Down);
+    Ok(LiquidityWithdrawal {
+        collateral_out: pro_rata(total_collateral)?,
+        fees_out: pro_rata(accrued_fees)?,
+        yes_remove: pro_rata(yes_reserve)?,
+        no_remove: pro_rata(no_reserve)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const USDC: u128 = 1_000_000;
+
+    #[test]
+    fn mul_div_rounds_as_requested() {
+        assert_eq!(mul_div(10, 10, 3, Rounding::Down), Ok(33));
+        assert_eq!(mul_div(10, 10, 3, Rounding::Up), Ok(34));
+        assert_eq!(mul_div(10, 9, 3, Rounding::Up), Ok(30));
+        assert_eq!(mul_div(1, 1, 0, Rounding::Down), Err(MathError::DivisionByZero));
+        assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Down), Ok(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 2, 1, Rounding::Down), Err(MathError::Overflow));
+    }
+
+    #[test]
+    fn buy_matches_reference_values() {
+        // 100/100 pool, 10 USDC at 2%: net 9.8 minted, then swapped
+        let quote = quote_buy(100 * USDC, 100 * USDC, 10 * USDC, 200, Side::Yes).unwrap();
+        assert_eq!(quote.fee, 200_000);
+        assert_eq!(quote.net_collateral, 9_800_000);
+        assert_eq!(quote.no_reserve, 119_600_000);
+        assert_eq!(quote.yes_reserve, 100_803_011);
+        assert_eq!(quote.tokens_out, 8_996_989);
+    }
+
+    #[test]
+    fn huge_reserves_do_not_overflow() {
+        // k would overflow u128 without the 256-bit product
+        let reserve = u128::MAX / 4;
+        let quote = quote_buy(reserve, reserve, USDC, 0, Side::No).unwrap();
+        assert!(quote.tokens_out > 0);
+        assert_eq!(
+            quote_buy(u128::MAX, 1, USDC, 0, Side::Yes),
+            Err(MathError::Overflow)
+        );
+    }
+
+    #[test]
+    fn empty_pool_prices_are_even() {
+        assert_eq!(spot_prices(0, 5), (PRICE_SCALE / 2, PRICE_SCALE / 2));
+        assert_eq!(spot_prices(u128::MAX, u128::MAX), (PRICE_SCALE / 2, PRICE_SCALE / 2));
+    }
+
+    #[test]
+    fn price_impact_is_relative_to_the_starting_price() {
+        assert_eq!(price_impact_bps(500_000, 550_000), Ok(1_000));
+        assert_eq!(price_impact_bps(500_000, 450_000), Ok(1_000));
+        assert_eq!(price_impact_bps(3, 4), Ok(3_334));
+        assert_eq!(price_impact_bps(0, 1), Err(MathError::DivisionByZero));
+    }
+
+    fn reserve() -> impl Strategy<Value = u128> {
+        1u128..1_000_000_000 * USDC
+    }
+
+    proptest! {
+        #[test]
+        fn buy_never_decreases_the_product(
+            yes in reserve(), no in reserve(), collateral in 0u128..100_000_000 * USDC,
+            fee_bps in 0u16..=1_000, buy_yes: bool,
+        ) {
+            let side = if buy_yes { Side::Yes } else { Side::No };
+            let q = quote_buy(yes, no, collateral, fee_bps, side).unwrap();
+            let yes_r = U256::from(yes + q.net_collateral);
+            let no_r = U256::from(no + q.net_collateral);
+            prop_assert!(U256::from(q.yes_reserve) * U256::from(q.no_reserve) >= yes_r * no_r);
+            prop_assert_eq!(q.fee + q.net_collateral, collateral);
+            let side_reserve = if buy_yes { yes } else { no };
+            prop_assert!(q.tokens_out <= q.net_collateral + side_reserve);
+        }
+
+        #[test]
+        fn sell_pays_at_most_tokens_in(
+            yes in reserve(), no in reserve(), tokens_in in 1u128..100_000_000 * USDC,
+            fee_bps in 0u16..=1_000, sell_yes: bool,
+        ) {
+            let side = if sell_yes { Side::Yes } else { Side::No };
+            let q = quote_sell(yes, no, tokens_in, fee_bps, side).unwrap();
+            prop_assert!(q.collateral_before_fee <= tokens_in);
+            prop_assert_eq!(q.collateral_out + q.fee, q.collateral_before_fee);
+            // Reserves after burning pairs still cover the pool's obligations
+            let before = U256::from(yes) * U256::from(no);
+            let (in_side, out_side) = if sell_yes {
+                (q.yes_reserve + q.collateral_before_fee, q.no_reserve)
+            } else {
+                (q.no_reserve + q.collateral_before_fee, q.yes_reserve)
+            };
+            prop_assert!(U256::from(in_side) * U256::from(out_side) >= before);
+        }
+
+        #[test]
+        fn buy_then_sell_is_not_profitable(
+            yes in reserve(), no in reserve(), collateral in 1u128..10_000_000 * USDC,
+            fee_bps in 0u16..=1_000, buy_yes: bool,
+        ) {
+            let side = if buy_yes { Side::Yes } else { Side::No };
+            let buy = quote_buy(yes, no, collateral, fee_bps, side).unwrap();
+            prop_assume!(buy.tokens_out > 0);
+            let sell = quote_sell(buy.yes_reserve, buy.no_reserve, buy.tokens_out, fee_bps, side).unwrap();
+            prop_assert!(sell.collateral_out <= collateral);
+        }
+
+        #[test]
+        fn fees_above_100_percent_are_rejected(
+            yes in reserve(), no in reserve(), amount in 0u128..100_000_000 * USDC,
+            fee_bps in 10_001u16..=u16::MAX, yes_side: bool,
+        ) {
+            let side = if yes_side { Side::Yes } else { Side::No };
+            prop_assert_eq!(quote_buy(yes, no, amount, fee_bps, side), Err(MathError::InvalidFee));
+            prop_assert_eq!(quote_sell(yes, no, amount, fee_bps, side), Err(MathError::InvalidFee));
+            prop_assert_eq!(fee_amount(amount, fee_bps), Err(MathError::InvalidFee));
+        }
+
+        #[test]
+        fn prices_sum_to_scale(yes in 0u128..u128::MAX, no in 0u128..u128::MAX) {
+            let (yes_price, no_price) = spot_prices(yes, no);
+            prop_assert!(yes_price + no_price <= PRICE_SCALE);
+            prop_assert!(yes_price + no_price >= PRICE_SCALE - 1);
+        }
+
+        #[test]
+        fn liquidity_round_trip_never_returns_more(
+            yes in reserve(), no in reserve(), collateral in reserve(), shares in reserve(),
+            amount in 1u128..1_000_000 * USDC,
+        ) {
+            let deposit = quote_add_liquidity(amount, yes, no, collateral, shares).unwrap();
+            let withdrawal = quote_remove_liquidity(
+                deposit.lp_shares,
+                shares + deposit.lp_shares,
+                collateral + amount,
+                0,
+                yes + deposit.yes_add,
+                no + deposit.no_add,
+            )
+            .unwrap();
+            prop_assert!(withdrawal.collateral_out <= amount);
+            prop_assert!(withdrawal.yes_remove <= deposit.yes_add + 1);
+            prop_assert!(withdrawal.no_remove <= deposit.no_add + 1);
+        }
+    }
+}
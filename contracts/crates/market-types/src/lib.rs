@@ -16,12 +16,55 @@ pub const USDC_ONE: u128 = 1_000_000;
 pub const MIN_INITIAL_LIQUIDITY: u128 = 10 * USDC_ONE;
 
 /// AMM price scale (1e6, matching USDC decimals for precision)
-pub const AMM_SCALE: u128 = 1_000_000;
+pub const AMM_SCALE: u128 = market_math::PRICE_SCALE;
 
 /// Default protocol fee: 2% = 200 basis points
 pub const DEFAULT_FEE_BPS: u16 = 200;
 pub const BPS_DENOMINATOR: u16 = 10_000;
 
+/// Price observations kept per market for TWAP queries (ring buffer size)
+pub const TWAP_OBSERVATION_CAPACITY: usize = 64;
+
+/// Minimum spacing between stored TWAP observations (5 minutes), so a burst of
+/// small trades cannot evict the history
+pub const TWAP_MIN_OBSERVATION_INTERVAL_NS: u64 = 5 * 60 * 1_000_000_000;
+
+/// Fee tiers creators may pick from until the owner changes them
+pub const DEFAULT_FEE_TIERS: [u16; 3] = [100, 200, 500];
+
+/// Upper bound for any fee tier: 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Default share of each trading fee routed to the protocol treasury (the rest goes to LPs)
+pub const DEFAULT_PROTOCOL_FEE_SHARE_BPS: u16 = 0;
+
+/// Default refundable bond a creator posts on top of the initial liquidity (disabled)
+pub const DEFAULT_CREATION_BOND: u128 = 0;
+
+/// Default share of each trading fee paid to the market creator
+pub const DEFAULT_CREATOR_FEE_SHARE_BPS: u16 = 0;
+
+/// Upper bound for the creator's share of each trading fee: 50%
+pub const MAX_CREATOR_FEE_SHARE_BPS: u16 = 5_000;
+
+/// Default delay between proposing and executing an admin action: 48 hours
+pub const DEFAULT_TIMELOCK_DELAY_NS: u64 = 48 * 60 * 60 * 1_000_000_000;
+
+/// Upper bound for the admin timelock delay: 30 days
+pub const MAX_TIMELOCK_DELAY_NS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Default window after the timelock in which an approved admin action can be executed: 7 days
+pub const DEFAULT_ACTION_TTL_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Default minimum bond for resolving a market before its resolution time: 100 USDC
+pub const DEFAULT_EARLY_RESOLUTION_BOND: u128 = 100 * USDC_ONE;
+
+/// Most markets one `redeem_all` call may redeem, to stay within the gas limit
+pub const MAX_BATCH_REDEMPTIONS: usize = 20;
+
+/// Longest window in which only a market's creator or designated resolver may resolve it: 7 days
+pub const MAX_RESOLUTION_PRIORITY_WINDOW_NS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000;
+
 // ── Enums ──────────────────────────────────────────────────────────────
 
 #[derive(
@@ -57,6 +100,36 @@ impl Outcome {
     }
 }
 
+impl From<Outcome> for market_math::Side {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Yes => market_math::Side::Yes,
+            Outcome::No => market_math::Side::No,
+        }
+    }
+}
+
+/// NEP-245 token id for an outcome position: `"{market_id}:yes"` or `"{market_id}:no"`.
+pub fn outcome_token_id(market_id: MarketId, outcome: Outcome) -> String {
+    match outcome {
+        Outcome::Yes => format!("{market_id}:yes"),
+        Outcome::No => format!("{market_id}:no"),
+    }
+}
+
+/// Inverse of [`outcome_token_id`]. Only the canonical form is accepted, so
+/// aliases such as `"07:yes"` or `"+7:yes"` cannot name the same token.
+pub fn parse_outcome_token_id(token_id: &str) -> Option<(MarketId, Outcome)> {
+    let (market_id, outcome) = token_id.split_once(':')?;
+    let outcome = match outcome {
+        "yes" => Outcome::Yes,
+        "no" => Outcome::No,
+        _ => return None,
+    };
+    let market_id: MarketId = market_id.parse().ok()?;
+    (outcome_token_id(market_id, outcome) == token_id).then_some((market_id, outcome))
+}
+
 #[derive(
     BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
 )]
@@ -69,6 +142,140 @@ pub enum MarketStatus {
     Settled,
 }
 
+/// Lifecycle of a market's creation bond: held until settlement, then refunded
+/// to the creator unless a curator slashed it for an invalid question.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum BondStatus {
+    NotRequired,
+    Held,
+    Refunded,
+    Slashed,
+}
+
+/// Who may create markets. In `Curated` mode markets are proposals until a
+/// curator approves them, unless the creator is allowlisted. In `Allowlist`
+/// mode only allowlisted creators may create markets.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum CreationMode {
+    Open,
+    Curated,
+    Allowlist,
+}
+
+/// Token standard used to gate access to a market.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TokenStandard {
+    /// Fungible token; balance read with `ft_balance_of`
+    Nep141,
+    /// Non-fungible token; count read with `nft_supply_for_owner`
+    Nep171,
+}
+
+/// Who may buy and add liquidity in a market. The creator always may; anyone may sell.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AccessPolicy {
+    Open,
+    /// Only accounts on the market's allowlist
+    Allowlist,
+    /// Only accounts holding at least `min_balance` of `token`
+    TokenGated {
+        token: AccountId,
+        standard: TokenStandard,
+        min_balance: U128,
+    },
+}
+
+/// Optional fee schedule that ramps the trading fee linearly from the market's
+/// base `fee_bps` up to `max_fee_bps` over the last `ramp_window_ns` before
+/// `resolution_time_ns`, when informed flow dominates.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct DynamicFeeConfig {
+    pub ramp_window_ns: U64,
+    pub max_fee_bps: u16,
+}
+
+/// Optional window after `resolution_time_ns` in which only the creator or
+/// `resolver` may submit the first resolution, before it opens to everyone.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResolutionPriority {
+    #[serde(default)]
+    pub resolver: Option<AccountId>,
+    pub window_ns: U64,
+}
+
+/// Optional caps that keep one account from cornering a thin market. Fixed at
+/// creation; `None` leaves a cap off.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TradeLimits {
+    /// Most collateral a single buy may spend or a sell may release, before fees
+    #[serde(default)]
+    pub max_trade_collateral: Option<U128>,
+    /// Most outcome tokens of one side an account may hold after a buy
+    #[serde(default)]
+    pub max_holding: Option<U128>,
+    /// Largest move of the traded outcome's price a single trade may cause
+    #[serde(default)]
+    pub max_price_impact_bps: Option<u16>,
+}
+
+/// What a resolution assertion claims. The oracle receives its JSON form
+/// (`preimage`) next to the hash, so disputers can read what is asserted.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResolutionClaim {
+    pub market_contract: AccountId,
+    pub market_id: U64,
+    pub question: String,
+    /// The market description, which states how the question resolves
+    pub resolution_criteria: String,
+    pub resolution_time_ns: U64,
+    pub asserted_outcome: Outcome,
+}
+
+impl ResolutionClaim {
+    /// Exact bytes hashed into the oracle claim.
+    pub fn preimage(&self) -> String {
+        near_sdk::serde_json::to_string(self).expect("Claim serializes to JSON")
+    }
+}
+
+/// One entry of an outcome-token `burn_batch` call.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OutcomeBurn {
+    pub market_id: MarketId,
+    pub outcome: Outcome,
+    /// `None` burns the account's whole balance
+    #[serde(default)]
+    pub amount: Option<U128>,
+}
+
 // ── Market Struct ──────────────────────────────────────────────────────
 
 #[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
@@ -81,6 +288,11 @@ pub struct Market {
     pub status: MarketStatus,
     pub outcome: Option<Outcome>,
 
+    // Contracts fixed at creation, so config changes only affect new markets
+    pub collateral_token: AccountId,
+    pub outcome_token: AccountId,
+    pub oracle: AccountId,
+
     // AMM reserves
     pub yes_reserve: u128,
     pub no_reserve: u128,
@@ -91,17 +303,112 @@ pub struct Market {
     // Collateral
     pub total_collateral: u128,
 
+    // Outcome tokens minted by this contract and not yet burned, pool included
+    pub yes_supply: u128,
+    pub no_supply: u128,
+
     // Fees
     pub fee_bps: u16,
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    /// LP share of fees not yet withdrawn
     pub accrued_fees: u128,
+    /// Lifetime protocol share of fees collected by this market
+    pub protocol_fees: u128,
+
+    // Creator
+    pub creation_bond: u128,
+    pub bond_status: BondStatus,
+    /// Curator's reason for slashing the bond, if given
+    pub bond_slash_reason: Option<String>,
+    pub creator_fee_share_bps: u16,
+    /// Creator share of fees not yet claimed
+    pub creator_fees: u128,
+
+    // Who may trade and add liquidity, and how much
+    pub access_policy: AccessPolicy,
+    pub limits: TradeLimits,
 
     // Oracle
+    pub resolution_priority: Option<ResolutionPriority>,
     pub assertion_id: Option<Bytes32>,
     pub asserted_outcome: Option<Outcome>,
     pub resolver: Option<AccountId>,
     pub disputer: Option<AccountId>,
     pub assertion_submitted_at_ns: Option<u64>,
     pub assertion_expires_at_ns: Option<u64>,
+    /// Whether the current assertion was made before `resolution_time_ns`
+    pub early_assertion: bool,
+    /// Claim behind the current assertion, kept after settlement
+    pub assertion_claim: Option<ResolutionClaim>,
+}
+
+// ── TWAP Observations ──────────────────────────────────────────────────
+
+/// Cumulative YES price (price × nanoseconds) as of `timestamp_ns`.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceObservation {
+    pub timestamp_ns: u64,
+    pub yes_price_cumulative: u128,
+}
+
+/// Ring buffer of the most recent `TWAP_OBSERVATION_CAPACITY` observations.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Default)]
+pub struct PriceObservations {
+    pub observations: Vec<PriceObservation>,
+    /// Slot the next observation overwrites once the buffer is full
+    pub next_index: u32,
+}
+
+impl PriceObservations {
+    pub fn latest(&self) -> Option<&PriceObservation> {
+        if self.observations.is_empty() {
+            return None;
+        }
+        let len = self.observations.len();
+        let idx = (self.next_index as usize + len - 1) % len;
+        self.observations.get(idx)
+    }
+
+    pub fn push(&mut self, observation: PriceObservation) {
+        if self.observations.len() < TWAP_OBSERVATION_CAPACITY {
+            self.observations.push(observation);
+            self.next_index = (self.observations.len() % TWAP_OBSERVATION_CAPACITY) as u32;
+        } else {
+            self.observations[self.next_index as usize] = observation;
+            self.next_index = ((self.next_index as usize + 1) % TWAP_OBSERVATION_CAPACITY) as u32;
+        }
+    }
+
+    /// Record `observation`, overwriting the latest one while it is less than
+    /// `TWAP_MIN_OBSERVATION_INTERVAL_NS` newer than the one before it. Every
+    /// stored observation but the latest then spans at least that interval.
+    pub fn record(&mut self, observation: PriceObservation) {
+        let len = self.observations.len();
+        if len >= 2 {
+            let latest = (self.next_index as usize + len - 1) % len;
+            let previous = (self.next_index as usize + len - 2) % len;
+            if self.observations[latest].timestamp_ns
+                < self.observations[previous].timestamp_ns + TWAP_MIN_OBSERVATION_INTERVAL_NS
+            {
+                self.observations[latest] = observation;
+                return;
+            }
+        }
+        self.push(observation);
+    }
+
+    /// Observations ordered oldest to newest.
+    pub fn chronological(&self) -> Vec<PriceObservation> {
+        let len = self.observations.len();
+        let start = if len < TWAP_OBSERVATION_CAPACITY {
+            0
+        } else {
+            self.next_index as usize
+        };
+        (0..len)
+            .map(|i| self.observations[(start + i) % len])
+            .collect()
+    }
 }
 
 // ── View Types ─────────────────────────────────────────────────────────
@@ -116,6 +423,9 @@ pub struct MarketView {
     pub resolution_time_ns: U64,
     pub status: MarketStatus,
     pub outcome: Option<Outcome>,
+    pub collateral_token: AccountId,
+    pub outcome_token: AccountId,
+    pub oracle: AccountId,
     pub yes_reserve: U128,
     pub no_reserve: U128,
     pub yes_price: U128,
@@ -123,7 +433,17 @@ pub struct MarketView {
     pub total_lp_shares: U128,
     pub total_collateral: U128,
     pub fee_bps: u16,
+    pub dynamic_fee: Option<DynamicFeeConfig>,
     pub accrued_fees: U128,
+    pub protocol_fees: U128,
+    pub creation_bond: U128,
+    pub bond_status: BondStatus,
+    pub bond_slash_reason: Option<String>,
+    pub creator_fee_share_bps: u16,
+    pub creator_fees: U128,
+    pub access_policy: AccessPolicy,
+    pub limits: TradeLimits,
+    pub resolution_priority: Option<ResolutionPriority>,
     pub assertion_id: Option<String>,
     pub asserted_outcome: Option<Outcome>,
     pub resolver: Option<AccountId>,
@@ -133,16 +453,53 @@ pub struct MarketView {
 }
 
 impl Market {
-    pub fn to_view(&self) -> MarketView {
-        let (yes_price, no_price) = if self.yes_reserve > 0 && self.no_reserve > 0 {
-            let total = self.yes_reserve + self.no_reserve;
-            (
-                self.no_reserve * AMM_SCALE / total,
-                self.yes_reserve * AMM_SCALE / total,
-            )
-        } else {
-            (AMM_SCALE / 2, AMM_SCALE / 2)
+    /// Spot (YES, NO) prices scaled by `AMM_SCALE`.
+    pub fn prices(&self) -> (u128, u128) {
+        market_math::spot_prices(self.yes_reserve, self.no_reserve)
+    }
+
+    /// Trading fee in effect at `now_ns`, including any dynamic ramp.
+    pub fn fee_bps_at(&self, now_ns: u64) -> u16 {
+        let Some(schedule) = self.dynamic_fee else {
+            return self.fee_bps;
         };
+        let window = schedule.ramp_window_ns.0;
+        let ramp_start = self.resolution_time_ns.saturating_sub(window);
+        if now_ns <= ramp_start || window == 0 {
+            return self.fee_bps;
+        }
+        if now_ns >= self.resolution_time_ns {
+            return schedule.max_fee_bps;
+        }
+
+        let elapsed = (now_ns - ramp_start) as u128;
+        let extra = (schedule.max_fee_bps - self.fee_bps) as u128 * elapsed / window as u128;
+        self.fee_bps + extra as u16
+    }
+
+    /// End of the resolution priority window, if the market has one.
+    pub fn priority_window_end_ns(&self) -> Option<u64> {
+        self.resolution_priority
+            .as_ref()
+            .map(|priority| self.resolution_time_ns.saturating_add(priority.window_ns.0))
+    }
+
+    /// Accounts allowed to resolve during the priority window; empty without one.
+    pub fn priority_resolvers(&self) -> Vec<AccountId> {
+        let Some(priority) = &self.resolution_priority else {
+            return Vec::new();
+        };
+        let mut resolvers = vec![self.creator.clone()];
+        if let Some(resolver) = &priority.resolver {
+            if *resolver != self.creator {
+                resolvers.push(resolver.clone());
+            }
+        }
+        resolvers
+    }
+
+    pub fn to_view(&self) -> MarketView {
+        let (yes_price, no_price) = self.prices();
 
         MarketView {
             id: U64(self.id),
@@ -152,6 +509,9 @@ impl Market {
             resolution_time_ns: U64(self.resolution_time_ns),
             status: self.status,
             outcome: self.outcome,
+            collateral_token: self.collateral_token.clone(),
+            outcome_token: self.outcome_token.clone(),
+            oracle: self.oracle.clone(),
             yes_reserve: U128(self.yes_reserve),
             no_reserve: U128(self.no_reserve),
             yes_price: U128(yes_price),
@@ -159,7 +519,17 @@ impl Market {
             total_lp_shares: U128(self.total_lp_shares),
             total_collateral: U128(self.total_collateral),
             fee_bps: self.fee_bps,
+            dynamic_fee: self.dynamic_fee,
             accrued_fees: U128(self.accrued_fees),
+            protocol_fees: U128(self.protocol_fees),
+            creation_bond: U128(self.creation_bond),
+            bond_status: self.bond_status,
+            bond_slash_reason: self.bond_slash_reason.clone(),
+            creator_fee_share_bps: self.creator_fee_share_bps,
+            creator_fees: U128(self.creator_fees),
+            access_policy: self.access_policy.clone(),
+            limits: self.limits,
+            resolution_priority: self.resolution_priority.clone(),
             assertion_id: self.assertion_id.map(hex::encode),
             asserted_outcome: self.asserted_outcome,
             resolver: self.resolver.clone(),
@@ -181,22 +551,58 @@ pub struct ResolutionStatusView {
     pub disputer: Option<AccountId>,
     pub assertion_submitted_at_ns: Option<U64>,
     pub assertion_expires_at_ns: Option<U64>,
+    pub is_early_assertion: bool,
+    /// Until this time only `priority_resolvers` may submit a resolution
+    pub priority_window_ends_at_ns: Option<U64>,
+    pub priority_resolvers: Vec<AccountId>,
+    /// JSON claim sent to the oracle and the hex keccak256 hash it asserted
+    pub claim_data: Option<String>,
+    pub claim_hash: Option<String>,
     pub now_ns: U64,
     pub is_disputable_now: bool,
     pub is_resolvable_now: bool,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TwapView {
+    pub market_id: U64,
+    pub window_start_ns: U64,
+    pub window_end_ns: U64,
+    pub yes_price: U128,
+    pub no_price: U128,
+}
+
 // ── FT Message Enums ───────────────────────────────────────────────────
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CreateMarketArgs {
+    pub question: String,
+    pub description: String,
+    pub resolution_time_ns: U64,
+    /// One of the approved fee tiers; defaults to the global default fee
+    #[serde(default)]
+    pub fee_bps: Option<u16>,
+    /// Optional fee ramp toward resolution
+    #[serde(default)]
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    /// Who may trade the market; defaults to `Open`
+    #[serde(default)]
+    pub access_policy: Option<AccessPolicy>,
+    /// Optional trade size, position and price impact caps
+    #[serde(default)]
+    pub limits: Option<TradeLimits>,
+    /// Optional window in which only the creator or a designated resolver may resolve
+    #[serde(default)]
+    pub resolution_priority: Option<ResolutionPriority>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 #[serde(tag = "action")]
 pub enum MarketFtMsg {
-    CreateMarket {
-        question: String,
-        description: String,
-        resolution_time_ns: U64,
-    },
+    CreateMarket(Box<CreateMarketArgs>),
     Buy {
         market_id: MarketId,
         outcome: Outcome,
@@ -211,6 +617,20 @@ pub enum MarketFtMsg {
     },
 }
 
+/// Trade held back until a token-gated market confirms the account's balance.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum GatedAction {
+    Buy {
+        outcome: Outcome,
+        collateral: U128,
+        min_tokens_out: U128,
+    },
+    AddLiquidity {
+        amount: U128,
+    },
+}
+
 // ── Config View ────────────────────────────────────────────────────────
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -222,4 +642,230 @@ pub struct ConfigView {
     pub oracle: AccountId,
     pub market_count: U64,
     pub default_fee_bps: u16,
+    pub fee_tiers: Vec<u16>,
+    pub protocol_fee_share_bps: u16,
+    pub treasurer: AccountId,
+    pub guardian: AccountId,
+    pub timelock_delay_ns: U64,
+    pub creation_bond: U128,
+    pub creator_fee_share_bps: u16,
+    pub curators: Vec<AccountId>,
+    pub creation_mode: CreationMode,
+    pub early_resolution_bond: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProtocolRevenueView {
+    pub token: AccountId,
+    /// Protocol fees held by the contract and available to withdraw
+    pub balance: U128,
+    /// Lifetime protocol fees across all markets using this token
+    pub total_accrued: U128,
+    pub total_withdrawn: U128,
+}
+
+// ── Admin Actions ──────────────────────────────────────────────────────
+
+/// Privileged operation that takes effect once enough approvers confirm it
+/// and the timelock delay has passed.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AdminAction {
+    SetOwner {
+        new_owner: AccountId,
+    },
+    SetGuardian {
+        guardian: AccountId,
+    },
+    SetTimelockDelay {
+        delay_ns: U64,
+    },
+    /// Replace the approver set and the number of approvals an action needs
+    SetApprovers {
+        approvers: Vec<AccountId>,
+        threshold: u32,
+    },
+    SetActionTtl {
+        ttl_ns: U64,
+    },
+    SetFeeTiers {
+        fee_tiers: Vec<u16>,
+    },
+    SetDefaultFeeBps {
+        default_fee_bps: u16,
+    },
+    SetProtocolFeeShareBps {
+        protocol_fee_share_bps: u16,
+    },
+    SetTreasurer {
+        treasurer: AccountId,
+    },
+    /// Bond required from creators of markets created afterwards; 0 disables it
+    SetCreationBond {
+        amount: U128,
+    },
+    /// Creator share of trading fees for markets created afterwards
+    SetCreatorFeeShareBps {
+        creator_fee_share_bps: u16,
+    },
+    /// Accounts allowed to slash creation bonds, review proposals and manage the creator allowlist
+    SetCurators {
+        curators: Vec<AccountId>,
+    },
+    SetCreationMode {
+        mode: CreationMode,
+    },
+    /// Minimum bond for resolving a market before its resolution time
+    SetEarlyResolutionBond {
+        amount: U128,
+    },
+    /// Oracle for markets created afterwards
+    SetOracle {
+        oracle: AccountId,
+    },
+    /// Outcome token ledger for markets created afterwards
+    SetOutcomeToken {
+        outcome_token: AccountId,
+    },
+    /// Collateral token for markets created afterwards
+    SetCollateralToken {
+        collateral_token: AccountId,
+    },
+    /// Withdraw tokens; limited to the surplus above what is owed unless `force`
+    EmergencyWithdrawToken {
+        token: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        force: bool,
+    },
+    EmergencyWithdrawNear {
+        receiver_id: AccountId,
+        amount: U128,
+    },
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct PendingAction {
+    pub action: AdminAction,
+    pub proposer: AccountId,
+    /// Approvers who confirmed the action, proposer included
+    pub approvals: Vec<AccountId>,
+    pub proposed_at_ns: u64,
+    pub executable_at_ns: u64,
+    pub expires_at_ns: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingActionView {
+    pub action_id: U64,
+    pub action: AdminAction,
+    pub proposer: AccountId,
+    pub approvals: Vec<AccountId>,
+    pub proposed_at_ns: U64,
+    pub executable_at_ns: U64,
+    pub expires_at_ns: U64,
+}
+
+impl PendingAction {
+    pub fn to_view(&self, action_id: u64) -> PendingActionView {
+        PendingActionView {
+            action_id: U64(action_id),
+            action: self.action.clone(),
+            proposer: self.proposer.clone(),
+            approvals: self.approvals.clone(),
+            proposed_at_ns: U64(self.proposed_at_ns),
+            executable_at_ns: U64(self.executable_at_ns),
+            expires_at_ns: U64(self.expires_at_ns),
+        }
+    }
+}
+
+/// M-of-N approval settings, passed to `new` and returned by `get_governance`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct GovernanceConfig {
+    pub approvers: Vec<AccountId>,
+    pub threshold: u32,
+    pub timelock_delay_ns: U64,
+    pub action_ttl_ns: U64,
+}
+
+// ── Market Proposals ───────────────────────────────────────────────────
+
+/// Market waiting for curator approval, with the deposit it will be created from.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug)]
+pub struct MarketProposal {
+    pub proposer: AccountId,
+    pub args: CreateMarketArgs,
+    /// Liquidity plus creation bond, refunded if the proposal is rejected
+    pub deposit: u128,
+    pub collateral_token: AccountId,
+    pub proposed_at_ns: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MarketProposalView {
+    pub proposal_id: U64,
+    pub proposer: AccountId,
+    pub question: String,
+    pub description: String,
+    pub resolution_time_ns: U64,
+    pub fee_bps: Option<u16>,
+    pub dynamic_fee: Option<DynamicFeeConfig>,
+    pub access_policy: Option<AccessPolicy>,
+    pub limits: Option<TradeLimits>,
+    pub resolution_priority: Option<ResolutionPriority>,
+    pub deposit: U128,
+    pub collateral_token: AccountId,
+    pub proposed_at_ns: U64,
+}
+
+impl MarketProposal {
+    pub fn to_view(&self, proposal_id: u64) -> MarketProposalView {
+        MarketProposalView {
+            proposal_id: U64(proposal_id),
+            proposer: self.proposer.clone(),
+            question: self.args.question.clone(),
+            description: self.args.description.clone(),
+            resolution_time_ns: self.args.resolution_time_ns,
+            fee_bps: self.args.fee_bps,
+            dynamic_fee: self.args.dynamic_fee,
+            access_policy: self.args.access_policy.clone(),
+            limits: self.args.limits,
+            resolution_priority: self.args.resolution_priority.clone(),
+            deposit: U128(self.deposit),
+            collateral_token: self.collateral_token.clone(),
+            proposed_at_ns: U64(self.proposed_at_ns),
+        }
+    }
+}
+
+// ── Solvency ───────────────────────────────────────────────────────────
+
+/// Result of `check_invariants`: the market's accounting plus each cross-check.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct InvariantReport {
+    pub market_id: U64,
+    pub yes_reserve: U128,
+    pub no_reserve: U128,
+    pub total_lp_shares: U128,
+    pub total_collateral: U128,
+    pub accrued_fees: U128,
+    pub yes_supply: U128,
+    pub no_supply: U128,
+    /// USDC the contract owes across all markets and the treasury
+    pub total_owed: U128,
+    /// LP shares exist exactly when the pool holds collateral
+    pub lp_shares_match_collateral: bool,
+    /// Both reserves are non-zero while an open market has liquidity
+    pub reserves_non_zero: bool,
+    /// Collateral covers redeeming every outstanding token of either outcome
+    pub collateral_covers_supply: bool,
+    /// This market's collateral, LP and creator fees and held bond are part of `total_owed`
+    pub owed_covers_market: bool,
+    pub ok: bool,
 }
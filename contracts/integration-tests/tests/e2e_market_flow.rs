@@ -3,6 +3,9 @@ use serde_json::json;
 const MOCK_FT_WASM: &str = "../target/near/mock_ft/mock_ft.wasm";
 const OUTCOME_TOKEN_WASM: &str = "../target/near/outcome_token/outcome_token.wasm";
 const MARKET_WASM: &str = "../target/near/market_contract/market_contract.wasm";
+const OUTCOME_WRAPPER_WASM: &str = "../target/near/outcome_wrapper/outcome_wrapper.wasm";
+const WRAPPER_FACTORY_WASM: &str =
+    "../target/near/outcome_wrapper_factory/outcome_wrapper_factory.wasm";
 
 const USDC_ONE: u128 = 1_000_000; // 6 decimals
 
@@ -10,7 +13,7 @@ async fn read_wasm(path: &str) -> Vec<u8> {
     let abs = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(path);
     tokio::fs::read(&abs).await.unwrap_or_else(|_| {
         panic!(
-            "WASM not found at: {}\nRun: cargo build --release --target wasm32-unknown-unknown -p mock-ft -p outcome-token -p market-contract",
+            "WASM not found at: {}\nRun: cargo build --release --target wasm32-unknown-unknown -p mock-ft -p outcome-token -p outcome-wrapper -p outcome-wrapper-factory -p market-contract",
             abs.display()
         )
     })
@@ -23,7 +26,7 @@ async fn storage_deposit(
     caller
         .call(token.id(), "storage_deposit")
         .args_json(json!({}))
-        .deposit(near_workspaces::types::NearToken::from_millinear(10))
+        .deposit(near_workspaces::types::NearToken::from_millinear(50))
         .transact()
         .await?
         .into_result()?;
@@ -83,6 +86,7 @@ async fn outcome_balance(
 
 /// Deploys and initializes all three contracts + creates test accounts with USDC.
 struct TestSetup {
+    sandbox: near_workspaces::Worker<near_workspaces::network::Sandbox>,
     usdc: near_workspaces::Contract,
     outcome_token: near_workspaces::Contract,
     market: near_workspaces::Contract,
@@ -109,16 +113,23 @@ async fn setup() -> anyhow::Result<TestSetup> {
 
     // Init outcome token
     outcome_token.call("new")
-        .args_json(json!({ "market_contract": market.id() }))
+        .args_json(json!({ "owner": owner.id(), "market_contract": market.id() }))
         .transact().await?.into_result()?;
 
-    // Init market (using market as oracle placeholder)
+    // Init market (using market as oracle placeholder). The owner is the sole
+    // approver and admin actions have no delay, so tests can apply them directly.
     market.call("new")
         .args_json(json!({
             "owner": owner.id(),
             "usdc_token": usdc.id(),
             "outcome_token": outcome_token.id(),
             "oracle": market.id(),
+            "governance": {
+                "approvers": [owner.id()],
+                "threshold": 1,
+                "timelock_delay_ns": "0",
+                "action_ttl_ns": "86400000000000",
+            },
         }))
         .transact().await?.into_result()?;
 
@@ -126,6 +137,8 @@ async fn setup() -> anyhow::Result<TestSetup> {
     storage_deposit(&alice, &usdc).await?;
     storage_deposit(&bob, &usdc).await?;
     storage_deposit(market.as_account(), &usdc).await?;
+    storage_deposit(&alice, &outcome_token).await?;
+    storage_deposit(&bob, &outcome_token).await?;
 
     // Mint USDC
     owner.call(usdc.id(), "mint")
@@ -135,7 +148,29 @@ async fn setup() -> anyhow::Result<TestSetup> {
         .args_json(json!({ "account_id": bob.id(), "amount": (1000 * USDC_ONE).to_string() }))
         .transact().await?.into_result()?;
 
-    Ok(TestSetup { usdc, outcome_token, market, owner, alice, bob })
+    Ok(TestSetup { sandbox, usdc, outcome_token, market, owner, alice, bob })
+}
+
+/// Propose an admin action as `approver` and return its id.
+async fn propose_action(
+    approver: &near_workspaces::Account,
+    market: &near_workspaces::Contract,
+    action: serde_json::Value,
+) -> anyhow::Result<String> {
+    let result = approver.call(market.id(), "propose_action")
+        .args_json(json!({ "action": action }))
+        .transact().await?.into_result()?;
+    Ok(result.json()?)
+}
+
+/// Propose and immediately execute an admin action as the sole approver.
+async fn admin_action(s: &TestSetup, action: serde_json::Value) -> anyhow::Result<()> {
+    let action_id = propose_action(&s.owner, &s.market, action).await?;
+    s.owner.call(s.market.id(), "execute_action")
+        .args_json(json!({ "action_id": action_id }))
+        .gas(near_workspaces::types::Gas::from_tgas(100))
+        .transact().await?.into_result()?;
+    Ok(())
 }
 
 fn future_time_ns() -> u64 {
@@ -413,3 +448,198 @@ async fn test_estimate_buy_matches_actual() -> anyhow::Result<()> {
     println!("test_estimate_buy_matches_actual PASSED (est={}, actual={})", estimated_tokens, actual_tokens);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_protocol_fee_withdrawal() -> anyhow::Result<()> {
+    let s = setup().await?;
+
+    // Route 50% of every trading fee to the treasury (owner is the default treasurer)
+    admin_action(&s, json!({ "SetProtocolFeeShareBps": { "protocol_fee_share_bps": 5_000 } })).await?;
+    storage_deposit(&s.owner, &s.usdc).await?;
+
+    ft_transfer_call(&s.alice, &s.usdc, &s.market, 100 * USDC_ONE, &json!({
+        "action": "CreateMarket",
+        "question": "Fee split test?",
+        "description": "",
+        "resolution_time_ns": future_time_ns().to_string(),
+    })).await?.into_result()?;
+
+    // 2% of 50 USDC = 1 USDC fee, half of it to the treasury
+    ft_transfer_call(&s.bob, &s.usdc, &s.market, 50 * USDC_ONE, &json!({
+        "action": "Buy",
+        "market_id": 0,
+        "outcome": "Yes",
+        "min_tokens_out": "0",
+    })).await?.into_result()?;
+
+    let revenue: serde_json::Value = s.market.view("get_protocol_revenue")
+        .args_json(json!({})).await?.json()?;
+    assert_eq!(revenue["balance"], (USDC_ONE / 2).to_string());
+
+    let result = s.owner.call(s.market.id(), "withdraw_protocol_fees")
+        .args_json(json!({ "amount": (USDC_ONE / 2).to_string() }))
+        .gas(near_workspaces::types::Gas::from_tgas(100))
+        .transact().await?;
+    assert!(result.is_success(), "Withdraw failed: {:?}", result.failures());
+    assert_eq!(ft_balance(&s.usdc, s.owner.id()).await?, USDC_ONE / 2);
+
+    let revenue: serde_json::Value = s.market.view("get_protocol_revenue")
+        .args_json(json!({})).await?.json()?;
+    assert_eq!(revenue["balance"], "0");
+    assert_eq!(revenue["total_withdrawn"], (USDC_ONE / 2).to_string());
+
+    println!("test_protocol_fee_withdrawal PASSED");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multisig_admin_action() -> anyhow::Result<()> {
+    let s = setup().await?;
+
+    // Move from a single owner key to 2-of-3 approvals
+    admin_action(&s, json!({ "SetApprovers": {
+        "approvers": [s.owner.id(), s.alice.id(), s.bob.id()],
+        "threshold": 2,
+    } })).await?;
+
+    let action_id = propose_action(&s.alice, &s.market, json!({
+        "SetTreasurer": { "treasurer": s.bob.id() },
+    })).await?;
+
+    let result = s.owner.call(s.market.id(), "execute_action")
+        .args_json(json!({ "action_id": action_id }))
+        .transact().await?;
+    assert!(result.is_failure(), "Executed with a single approval");
+
+    s.bob.call(s.market.id(), "approve_action")
+        .args_json(json!({ "action_id": action_id }))
+        .transact().await?.into_result()?;
+    s.owner.call(s.market.id(), "execute_action")
+        .args_json(json!({ "action_id": action_id }))
+        .transact().await?.into_result()?;
+
+    let config: serde_json::Value = s.market.view("get_config")
+        .args_json(json!({})).await?.json()?;
+    assert_eq!(config["treasurer"], s.bob.id().to_string());
+    let pending: Vec<serde_json::Value> = s.market.view("get_pending_actions")
+        .args_json(json!({})).await?.json()?;
+    assert!(pending.is_empty());
+
+    println!("test_multisig_admin_action PASSED");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wrap_and_unwrap_outcome_tokens() -> anyhow::Result<()> {
+    let s = setup().await?;
+
+    ft_transfer_call(&s.alice, &s.usdc, &s.market, 100 * USDC_ONE, &json!({
+        "action": "CreateMarket",
+        "question": "Wrapper test?",
+        "description": "",
+        "resolution_time_ns": future_time_ns().to_string(),
+    })).await?.into_result()?;
+    ft_transfer_call(&s.bob, &s.usdc, &s.market, 50 * USDC_ONE, &json!({
+        "action": "Buy",
+        "market_id": 0,
+        "outcome": "Yes",
+        "min_tokens_out": "0",
+    })).await?.into_result()?;
+    let position = outcome_balance(&s.outcome_token, 0, "Yes", s.bob.id()).await?;
+
+    let wrapper = s.sandbox.dev_deploy(&read_wasm(OUTCOME_WRAPPER_WASM).await).await?;
+    wrapper.call("new")
+        .args_json(json!({
+            "outcome_token": s.outcome_token.id(),
+            "market_contract": s.market.id(),
+            "market_id": 0,
+            "outcome": "Yes",
+        }))
+        .transact().await?.into_result()?;
+    storage_deposit(&s.bob, &wrapper).await?;
+    storage_deposit(wrapper.as_account(), &s.outcome_token).await?;
+
+    // Wrap: move the position to the wrapper with mt_transfer_call
+    let result = s.bob.call(s.outcome_token.id(), "mt_transfer_call")
+        .args_json(json!({
+            "receiver_id": wrapper.id(),
+            "token_id": "0:yes",
+            "amount": position.to_string(),
+            "msg": "",
+        }))
+        .deposit(near_workspaces::types::NearToken::from_yoctonear(1))
+        .gas(near_workspaces::types::Gas::from_tgas(150))
+        .transact().await?;
+    assert!(result.is_success(), "Wrap failed: {:?}", result.failures());
+    assert_eq!(ft_balance(&wrapper, s.bob.id()).await?, position);
+    assert_eq!(outcome_balance(&s.outcome_token, 0, "Yes", s.bob.id()).await?, 0);
+    assert_eq!(outcome_balance(&s.outcome_token, 0, "Yes", wrapper.id()).await?, position);
+
+    // Metadata picks up the market question
+    wrapper.call("refresh_metadata")
+        .gas(near_workspaces::types::Gas::from_tgas(50))
+        .transact().await?.into_result()?;
+    let metadata: serde_json::Value = wrapper.view("ft_metadata").args_json(json!({})).await?.json()?;
+    assert_eq!(metadata["name"], "YES — Wrapper test?");
+
+    // Unwrap half back into the outcome-token ledger
+    let result = s.bob.call(wrapper.id(), "unwrap")
+        .args_json(json!({ "amount": (position / 2).to_string() }))
+        .deposit(near_workspaces::types::NearToken::from_yoctonear(1))
+        .gas(near_workspaces::types::Gas::from_tgas(100))
+        .transact().await?;
+    assert!(result.is_success(), "Unwrap failed: {:?}", result.failures());
+    assert_eq!(ft_balance(&wrapper, s.bob.id()).await?, position - position / 2);
+    assert_eq!(outcome_balance(&s.outcome_token, 0, "Yes", s.bob.id()).await?, position / 2);
+
+    println!("test_wrap_and_unwrap_outcome_tokens PASSED");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_wrapper_factory_creates_position_token() -> anyhow::Result<()> {
+    let s = setup().await?;
+
+    ft_transfer_call(&s.alice, &s.usdc, &s.market, 100 * USDC_ONE, &json!({
+        "action": "CreateMarket",
+        "question": "Factory test?",
+        "description": "",
+        "resolution_time_ns": future_time_ns().to_string(),
+    })).await?.into_result()?;
+
+    let factory = s.sandbox.dev_deploy(&read_wasm(WRAPPER_FACTORY_WASM).await).await?;
+    factory.call("new")
+        .args_json(json!({
+            "owner": factory.id(),
+            "outcome_token": s.outcome_token.id(),
+            "market_contract": s.market.id(),
+        }))
+        .transact().await?.into_result()?;
+    factory.as_account().call(factory.id(), "set_wrapper_code")
+        .args(read_wasm(OUTCOME_WRAPPER_WASM).await)
+        .gas(near_workspaces::types::Gas::from_tgas(300))
+        .transact().await?.into_result()?;
+
+    let deposit: String = factory.view("get_wrapper_deposit").args_json(json!({})).await?.json()?;
+    let result = s.bob.call(factory.id(), "create_wrapper")
+        .args_json(json!({ "market_id": 0, "outcome": "No" }))
+        .deposit(near_workspaces::types::NearToken::from_yoctonear(deposit.parse()?))
+        .gas(near_workspaces::types::Gas::from_tgas(200))
+        .transact().await?;
+    assert!(result.is_success(), "Create failed: {:?}", result.failures());
+
+    let wrapper_id: near_workspaces::AccountId = factory.view("get_wrapper")
+        .args_json(json!({ "market_id": 0, "outcome": "No" }))
+        .await?.json()?;
+    assert_eq!(wrapper_id.as_str(), format!("0-no.{}", factory.id()));
+
+    let metadata: serde_json::Value = s.sandbox.view(&wrapper_id, "ft_metadata")
+        .args_json(json!({})).await?.json()?;
+    assert_eq!(metadata["name"], "NO — Factory test?");
+    let registered: serde_json::Value = s.outcome_token.view("storage_balance_of")
+        .args_json(json!({ "account_id": wrapper_id })).await?.json()?;
+    assert!(!registered.is_null());
+
+    println!("test_wrapper_factory_creates_position_token PASSED");
+    Ok(())
+}
@@ -0,0 +1,328 @@
+use near_sdk::json_types::U128;
+use near_sdk::{
+    env, near, require, AccountId, Gas, NearToken, Promise, PromiseOrValue, PromiseResult,
+};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt};
+
+const GAS_FOR_BALANCE_CHECK: Gas = Gas::from_tgas(5);
+const GAS_FOR_ACCESS_CALLBACK: Gas = Gas::from_tgas(60);
+
+#[near]
+impl MarketContract {
+    // ── Access Management ──────────────────────────────────────────────
+
+    /// Change who may buy and add liquidity in a market. Creator only. Selling,
+    /// removing liquidity and redeeming stay open, so a tighter policy never
+    /// locks existing holders in.
+    pub fn set_access_policy(&mut self, market_id: MarketId, policy: AccessPolicy) {
+        let mut market = self.assert_market_creator(market_id);
+        Self::validate_access_policy(&policy);
+        market.access_policy = policy;
+
+        Event::AccessPolicyUpdated {
+            market_id,
+            policy: &market.access_policy,
+        }
+        .emit();
+
+        self.markets.insert(market_id, market);
+    }
+
+    /// Add and remove accounts on a market's allowlist. Creator only.
+    pub fn update_market_allowlist(
+        &mut self,
+        market_id: MarketId,
+        add: Vec<AccountId>,
+        remove: Vec<AccountId>,
+    ) {
+        self.assert_market_creator(market_id);
+        for account_id in &add {
+            self.market_allowlists
+                .insert(Self::lp_key(market_id, account_id));
+        }
+        for account_id in &remove {
+            self.market_allowlists
+                .remove(&Self::lp_key(market_id, account_id));
+        }
+
+        Event::MarketAllowlistUpdated {
+            market_id,
+            added: &add,
+            removed: &remove,
+        }
+        .emit();
+    }
+
+    pub fn is_on_market_allowlist(&self, market_id: MarketId, account_id: AccountId) -> bool {
+        self.market_allowlists
+            .contains(&Self::lp_key(market_id, &account_id))
+    }
+
+    // ── Token Gate Callback ────────────────────────────────────────────
+
+    /// Run a held-back trade once the gate token balance is known. Panics when
+    /// the account holds too little, so an `ft_transfer_call` deposit is refunded.
+    #[private]
+    pub fn on_access_checked(
+        &mut self,
+        market_id: MarketId,
+        account_id: AccountId,
+        action: GatedAction,
+    ) -> PromiseOrValue<U128> {
+        let balance = match env::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                near_sdk::serde_json::from_slice::<U128>(&bytes)
+                    .expect("Invalid gate token balance")
+                    .0
+            }
+            _ => env::panic_str("Failed to check gate token balance"),
+        };
+        let market = self.markets.get(&market_id).expect("Market not found");
+        if let AccessPolicy::TokenGated { min_balance, .. } = &market.access_policy {
+            require!(
+                balance >= min_balance.0,
+                "Account does not hold the market's access token"
+            );
+        }
+
+        self.execute_gated(market_id, account_id, action)
+    }
+}
+
+impl MarketContract {
+    fn assert_market_creator(&self, market_id: MarketId) -> Market {
+        let market = self.markets.get(&market_id).expect("Market not found");
+        require!(
+            env::predecessor_account_id() == market.creator,
+            "Only the market creator can manage access"
+        );
+        market.clone()
+    }
+
+    pub(crate) fn validate_access_policy(policy: &AccessPolicy) {
+        if let AccessPolicy::TokenGated { min_balance, .. } = policy {
+            require!(
+                min_balance.0 > 0,
+                "Gate token minimum balance must be positive"
+            );
+        }
+    }
+
+    /// Run `action` for `account_id` if the market's access policy allows it.
+    /// Token-gated markets first read the account's balance and run the action
+    /// in `on_access_checked`; the returned promise resolves to the unused deposit.
+    pub(crate) fn internal_gated(
+        &mut self,
+        market_id: MarketId,
+        account_id: AccountId,
+        action: GatedAction,
+    ) -> PromiseOrValue<U128> {
+        let market = self.markets.get(&market_id).expect("Market not found");
+        let gate = match &market.access_policy {
+            // The creator can always trade their own market
+            _ if account_id == market.creator => None,
+            AccessPolicy::Open => None,
+            AccessPolicy::Allowlist => {
+                require!(
+                    self.market_allowlists
+                        .contains(&Self::lp_key(market_id, &account_id)),
+                    "Account is not allowed to trade this market"
+                );
+                None
+            }
+            AccessPolicy::TokenGated {
+                token, standard, ..
+            } => Some((token.clone(), *standard)),
+        };
+
+        let Some((token, standard)) = gate else {
+            return self.execute_gated(market_id, account_id, action);
+        };
+
+        let method = match standard {
+            TokenStandard::Nep141 => "ft_balance_of",
+            TokenStandard::Nep171 => "nft_supply_for_owner",
+        };
+        PromiseOrValue::Promise(
+            Promise::new(token)
+                .function_call(
+                    method.to_string(),
+                    near_sdk::serde_json::json!({ "account_id": account_id })
+                        .to_string()
+                        .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_BALANCE_CHECK,
+                )
+                .then(
+                    Promise::new(env::current_account_id()).function_call(
+                        "on_access_checked".to_string(),
+                        near_sdk::serde_json::json!({
+                            "market_id": market_id,
+                            "account_id": account_id,
+                            "action": action,
+                        })
+                        .to_string()
+                        .into_bytes(),
+                        NearToken::from_yoctonear(0),
+                        GAS_FOR_ACCESS_CALLBACK,
+                    ),
+                ),
+        )
+    }
+
+    fn execute_gated(
+        &mut self,
+        market_id: MarketId,
+        account_id: AccountId,
+        action: GatedAction,
+    ) -> PromiseOrValue<U128> {
+        match action {
+            GatedAction::Buy {
+                outcome,
+                collateral,
+                min_tokens_out,
+            } => {
+                self.internal_capped_buy(market_id, outcome, collateral, min_tokens_out, account_id)
+            }
+            GatedAction::AddLiquidity { amount } => {
+                self.internal_add_liquidity(market_id, amount.0, account_id);
+                PromiseOrValue::Value(U128(0))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn test_contract(policy: AccessPolicy) -> MarketContract {
+        ContractBuilder::new()
+            .market_with(CreateMarketArgs {
+                access_policy: Some(policy),
+                ..market_args()
+            })
+            .build()
+    }
+
+    fn buy(contract: &mut MarketContract, buyer: &str) -> PromiseOrValue<U128> {
+        set_context("usdc.testnet", vec![]);
+        contract.ft_on_transfer(
+            account(buyer),
+            U128(10 * USDC_ONE),
+            near_sdk::serde_json::json!({
+                "action": "Buy",
+                "market_id": 0,
+                "outcome": "Yes",
+                "min_tokens_out": "0",
+            })
+            .to_string(),
+        )
+    }
+
+    fn gated_policy() -> AccessPolicy {
+        AccessPolicy::TokenGated {
+            token: account("badge.testnet"),
+            standard: TokenStandard::Nep171,
+            min_balance: U128(1),
+        }
+    }
+
+    fn balance_result(balance: u128) -> PromiseResult {
+        PromiseResult::Successful(near_sdk::serde_json::to_vec(&U128(balance)).unwrap())
+    }
+
+    #[test]
+    fn allowlisted_account_can_buy() {
+        let mut contract = test_contract(AccessPolicy::Allowlist);
+        set_context("creator.testnet", vec![]);
+        contract.update_market_allowlist(0, vec![account("alice.testnet")], vec![]);
+        assert!(contract.is_on_market_allowlist(0, account("alice.testnet")));
+
+        buy(&mut contract, "alice.testnet");
+        assert!(contract.markets.get(&0).unwrap().yes_supply > 50 * USDC_ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is not allowed to trade this market")]
+    fn allowlist_blocks_other_accounts() {
+        let mut contract = test_contract(AccessPolicy::Allowlist);
+        buy(&mut contract, "mallory.testnet");
+    }
+
+    #[test]
+    fn removed_holder_can_still_sell() {
+        let mut contract = test_contract(AccessPolicy::Allowlist);
+        set_context("creator.testnet", vec![]);
+        contract.update_market_allowlist(0, vec![account("alice.testnet")], vec![]);
+        buy(&mut contract, "alice.testnet");
+        let yes_supply = contract.markets.get(&0).unwrap().yes_supply;
+
+        set_context("creator.testnet", vec![]);
+        contract.update_market_allowlist(0, vec![], vec![account("alice.testnet")]);
+        set_context("alice.testnet", vec![]);
+        contract.sell(0, Outcome::Yes, U128(USDC_ONE), U128(0));
+        assert!(contract.markets.get(&0).unwrap().yes_supply < yes_supply);
+    }
+
+    #[test]
+    fn token_gated_buy_waits_for_balance_check() {
+        let mut contract = test_contract(gated_policy());
+        let result = buy(&mut contract, "alice.testnet");
+        assert!(matches!(result, PromiseOrValue::Promise(_)));
+        assert_eq!(contract.markets.get(&0).unwrap().yes_supply, 50 * USDC_ONE);
+
+        set_context("market.testnet", vec![balance_result(1)]);
+        contract.on_access_checked(
+            0,
+            account("alice.testnet"),
+            GatedAction::Buy {
+                outcome: Outcome::Yes,
+                collateral: U128(10 * USDC_ONE),
+                min_tokens_out: U128(0),
+            },
+        );
+        assert!(contract.markets.get(&0).unwrap().yes_supply > 50 * USDC_ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Account does not hold the market's access token")]
+    fn token_gated_buy_rejected_without_balance() {
+        let mut contract = test_contract(gated_policy());
+        set_context("market.testnet", vec![balance_result(0)]);
+        contract.on_access_checked(
+            0,
+            account("alice.testnet"),
+            GatedAction::AddLiquidity {
+                amount: U128(10 * USDC_ONE),
+            },
+        );
+    }
+
+    #[test]
+    fn creator_bypasses_gate_and_manages_policy() {
+        let mut contract = test_contract(gated_policy());
+        let result = buy(&mut contract, "creator.testnet");
+        assert!(matches!(result, PromiseOrValue::Value(U128(0))));
+
+        set_context("creator.testnet", vec![]);
+        contract.set_access_policy(0, AccessPolicy::Open);
+        assert_eq!(
+            contract.get_market(0).unwrap().access_policy,
+            AccessPolicy::Open
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the market creator can manage access")]
+    fn only_creator_sets_policy() {
+        let mut contract = test_contract(AccessPolicy::Allowlist);
+        set_context("mallory.testnet", vec![]);
+        contract.set_access_policy(0, AccessPolicy::Open);
+    }
+}
@@ -0,0 +1,587 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, AccountId, NearToken, Promise};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt};
+
+const DEFAULT_ACTIONS_LIMIT: u32 = 50;
+
+#[near]
+impl MarketContract {
+    // ── Timelocked Admin Actions ───────────────────────────────────────
+    // 1. An approver proposes an action; it is queued and emitted
+    // 2. Other approvers confirm it until the threshold is met
+    // 3. Guardian may cancel it while it waits; anyone may clear it once expired
+    // 4. An approver executes it after the timelock delay, before it expires
+
+    /// Queue a privileged action, counting the proposer's approval. Approver-only.
+    /// Returns the action id.
+    pub fn propose_action(&mut self, action: AdminAction) -> U64 {
+        let proposer = self.assert_approver();
+        Self::validate_action(&action);
+
+        let action_id = self.next_action_id;
+        self.next_action_id += 1;
+        let now = env::block_timestamp();
+        let executable_at_ns = now + self.timelock_delay_ns;
+        let pending = PendingAction {
+            action,
+            proposer: proposer.clone(),
+            approvals: vec![proposer],
+            proposed_at_ns: now,
+            executable_at_ns,
+            expires_at_ns: executable_at_ns + self.action_ttl_ns,
+        };
+
+        Event::AdminActionProposed {
+            action_id: U64(action_id),
+            action: &pending.action,
+            proposer: &pending.proposer,
+            executable_at_ns: U64(pending.executable_at_ns),
+        }
+        .emit();
+
+        self.pending_actions.insert(action_id, pending);
+        U64(action_id)
+    }
+
+    /// Confirm a queued action. Approver-only.
+    pub fn approve_action(&mut self, action_id: U64) {
+        let approver = self.assert_approver();
+        let pending = self
+            .pending_actions
+            .get_mut(&action_id.0)
+            .expect("Admin action not found");
+        require!(
+            env::block_timestamp() < pending.expires_at_ns,
+            "Admin action has expired"
+        );
+        require!(
+            !pending.approvals.contains(&approver),
+            "Admin action already approved"
+        );
+        pending.approvals.push(approver.clone());
+        let approvals = pending.approvals.len() as u32;
+
+        Event::AdminActionApproved {
+            action_id,
+            approver: &approver,
+            approvals,
+        }
+        .emit();
+    }
+
+    /// Apply a queued action once it has enough approvals and its delay has
+    /// passed. Approver-only. A surplus-checked token withdrawal stays queued
+    /// until `on_emergency_balance` confirms and executes it.
+    pub fn execute_action(&mut self, action_id: U64) {
+        self.assert_approver();
+        let pending = self
+            .pending_actions
+            .get(&action_id.0)
+            .expect("Admin action not found");
+        let now = env::block_timestamp();
+        require!(now >= pending.executable_at_ns, "Timelock has not expired");
+        require!(now < pending.expires_at_ns, "Admin action has expired");
+        require!(
+            self.current_approvals(pending) >= self.approval_threshold,
+            "Not enough approvals"
+        );
+        if let AdminAction::EmergencyWithdrawToken { force: false, .. } = &pending.action {
+            self.internal_emergency_surplus_withdraw(action_id);
+            return;
+        }
+        let action = pending.action.clone();
+        self.pending_actions.remove(&action_id.0);
+
+        Event::AdminActionExecuted {
+            action_id,
+            action: &action,
+        }
+        .emit();
+        self.apply_action(action);
+    }
+
+    /// Drop a queued action. Callable by the guardian, and by anyone once the
+    /// action has expired.
+    pub fn cancel_action(&mut self, action_id: U64) {
+        let caller = env::predecessor_account_id();
+        let pending = self
+            .pending_actions
+            .get(&action_id.0)
+            .expect("Admin action not found");
+        require!(
+            caller == self.guardian || env::block_timestamp() >= pending.expires_at_ns,
+            "Only guardian can cancel a pending admin action"
+        );
+        self.pending_actions.remove(&action_id.0);
+
+        Event::AdminActionCancelled {
+            action_id,
+            cancelled_by: &caller,
+        }
+        .emit();
+    }
+
+    // ── Views ──────────────────────────────────────────────────────────
+
+    pub fn get_governance(&self) -> GovernanceConfig {
+        GovernanceConfig {
+            approvers: self.approvers.clone(),
+            threshold: self.approval_threshold,
+            timelock_delay_ns: U64(self.timelock_delay_ns),
+            action_ttl_ns: U64(self.action_ttl_ns),
+        }
+    }
+
+    pub fn get_pending_action(&self, action_id: U64) -> Option<PendingActionView> {
+        self.pending_actions
+            .get(&action_id.0)
+            .map(|pending| pending.to_view(action_id.0))
+    }
+
+    /// Queued actions in storage order, paginated without loading the whole map.
+    pub fn get_pending_actions(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<PendingActionView> {
+        self.pending_actions
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(DEFAULT_ACTIONS_LIMIT) as usize)
+            .map(|(action_id, pending)| pending.to_view(*action_id))
+            .collect()
+    }
+}
+
+impl MarketContract {
+    fn assert_approver(&self) -> AccountId {
+        let caller = env::predecessor_account_id();
+        require!(
+            self.approvers.contains(&caller),
+            "Only approvers can call this method"
+        );
+        caller
+    }
+
+    /// Approvals from accounts that are still approvers.
+    fn current_approvals(&self, pending: &PendingAction) -> u32 {
+        pending
+            .approvals
+            .iter()
+            .filter(|approver| self.approvers.contains(approver))
+            .count() as u32
+    }
+
+    pub(crate) fn validate_approvers(approvers: &[AccountId], threshold: u32) {
+        require!(!approvers.is_empty(), "Approvers cannot be empty");
+        require!(
+            (1..=approvers.len()).all(|i| !approvers[i..].contains(&approvers[i - 1])),
+            "Approvers must be unique"
+        );
+        require!(
+            threshold >= 1 && threshold as usize <= approvers.len(),
+            "Threshold must be between 1 and the number of approvers"
+        );
+    }
+
+    /// Stateless checks run at proposal time; state-dependent checks run on execution.
+    fn validate_action(action: &AdminAction) {
+        match action {
+            AdminAction::SetTimelockDelay { delay_ns } => require!(
+                delay_ns.0 <= MAX_TIMELOCK_DELAY_NS,
+                "Timelock delay exceeds maximum"
+            ),
+            AdminAction::SetApprovers {
+                approvers,
+                threshold,
+            } => Self::validate_approvers(approvers, *threshold),
+            AdminAction::SetActionTtl { ttl_ns } => {
+                require!(ttl_ns.0 > 0, "Action TTL must be positive")
+            }
+            AdminAction::SetEarlyResolutionBond { amount } => {
+                require!(amount.0 > 0, "Early resolution bond must be positive")
+            }
+            AdminAction::EmergencyWithdrawToken { amount, .. }
+            | AdminAction::EmergencyWithdrawNear { amount, .. } => {
+                require!(amount.0 > 0, "Amount must be positive")
+            }
+            AdminAction::SetOwner { .. }
+            | AdminAction::SetGuardian { .. }
+            | AdminAction::SetFeeTiers { .. }
+            | AdminAction::SetDefaultFeeBps { .. }
+            | AdminAction::SetProtocolFeeShareBps { .. }
+            | AdminAction::SetTreasurer { .. }
+            | AdminAction::SetCreationBond { .. }
+            | AdminAction::SetCreatorFeeShareBps { .. }
+            | AdminAction::SetCurators { .. }
+            | AdminAction::SetCreationMode { .. }
+            | AdminAction::SetOracle { .. }
+            | AdminAction::SetOutcomeToken { .. }
+            | AdminAction::SetCollateralToken { .. } => {}
+        }
+    }
+
+    fn apply_action(&mut self, action: AdminAction) {
+        match action {
+            AdminAction::SetOwner { new_owner } => {
+                Event::OwnerChanged {
+                    old_owner: &self.owner,
+                    new_owner: &new_owner,
+                }
+                .emit();
+                self.owner = new_owner;
+            }
+            AdminAction::SetGuardian { guardian } => {
+                Event::GuardianChanged {
+                    old_guardian: &self.guardian,
+                    new_guardian: &guardian,
+                }
+                .emit();
+                self.guardian = guardian;
+            }
+            AdminAction::SetTimelockDelay { delay_ns } => {
+                self.timelock_delay_ns = delay_ns.0;
+                Event::TimelockDelayUpdated { delay_ns }.emit();
+            }
+            AdminAction::SetApprovers {
+                approvers,
+                threshold,
+            } => {
+                Event::ApproversUpdated {
+                    approvers: &approvers,
+                    threshold,
+                }
+                .emit();
+                self.approvers = approvers;
+                self.approval_threshold = threshold;
+            }
+            AdminAction::SetActionTtl { ttl_ns } => {
+                self.action_ttl_ns = ttl_ns.0;
+                Event::ActionTtlUpdated { ttl_ns }.emit();
+            }
+            AdminAction::SetFeeTiers { fee_tiers } => self.set_fee_tiers(fee_tiers),
+            AdminAction::SetDefaultFeeBps { default_fee_bps } => {
+                self.set_default_fee_bps(default_fee_bps)
+            }
+            AdminAction::SetProtocolFeeShareBps {
+                protocol_fee_share_bps,
+            } => self.set_protocol_fee_share_bps(protocol_fee_share_bps),
+            AdminAction::SetTreasurer { treasurer } => self.set_treasurer(treasurer),
+            AdminAction::SetCreationBond { amount } => self.set_creation_bond(amount),
+            AdminAction::SetCreatorFeeShareBps {
+                creator_fee_share_bps,
+            } => self.set_creator_fee_share_bps(creator_fee_share_bps),
+            AdminAction::SetCurators { curators } => self.set_curators(curators),
+            AdminAction::SetCreationMode { mode } => self.set_creation_mode(mode),
+            AdminAction::SetEarlyResolutionBond { amount } => {
+                self.set_early_resolution_bond(amount)
+            }
+            AdminAction::SetOracle { oracle } => {
+                Event::OracleUpdated {
+                    old_oracle: &self.oracle,
+                    new_oracle: &oracle,
+                }
+                .emit();
+                self.oracle = oracle;
+            }
+            AdminAction::SetOutcomeToken { outcome_token } => {
+                Event::OutcomeTokenUpdated {
+                    old_outcome_token: &self.outcome_token,
+                    new_outcome_token: &outcome_token,
+                }
+                .emit();
+                self.outcome_token = outcome_token;
+            }
+            AdminAction::SetCollateralToken { collateral_token } => {
+                Event::CollateralTokenUpdated {
+                    old_collateral_token: &self.usdc_token,
+                    new_collateral_token: &collateral_token,
+                }
+                .emit();
+                self.usdc_token = collateral_token;
+            }
+            AdminAction::EmergencyWithdrawToken {
+                token,
+                receiver_id,
+                amount,
+                force,
+            } => {
+                // Unforced withdrawals are applied by `on_emergency_balance`
+                self.internal_emergency_transfer(token, receiver_id, amount, force);
+            }
+            AdminAction::EmergencyWithdrawNear {
+                receiver_id,
+                amount,
+            } => self.internal_emergency_withdraw_near(receiver_id, amount),
+        }
+    }
+
+    fn internal_emergency_withdraw_near(&self, receiver_id: AccountId, amount: U128) {
+        require!(
+            env::account_balance() >= NearToken::from_yoctonear(amount.0),
+            "Insufficient balance"
+        );
+
+        Event::EmergencyNearWithdrawal {
+            receiver_id: &receiver_id,
+            amount,
+        }
+        .emit();
+
+        Promise::new(receiver_id).transfer(NearToken::from_yoctonear(amount.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::test_utils::get_logs;
+
+    const HOUR_NS: u64 = 60 * 60 * 1_000_000_000;
+
+    fn setup() -> MarketContract {
+        ContractBuilder::new().guardian("guardian.testnet").build()
+    }
+
+    /// 2-of-3 approvers, 1 hour timelock, 1 day to execute.
+    fn setup_multisig() -> MarketContract {
+        ContractBuilder::new()
+            .governance(GovernanceConfig {
+                approvers: vec![
+                    account("a.testnet"),
+                    account("b.testnet"),
+                    account("c.testnet"),
+                ],
+                threshold: 2,
+                timelock_delay_ns: U64(HOUR_NS),
+                action_ttl_ns: U64(24 * HOUR_NS),
+            })
+            .build()
+    }
+
+    fn set_owner(new_owner: &str) -> AdminAction {
+        AdminAction::SetOwner {
+            new_owner: account(new_owner),
+        }
+    }
+
+    #[test]
+    fn config_changes_only_apply_to_new_markets() {
+        let mut contract = ContractBuilder::new().market().build();
+
+        for action in [
+            AdminAction::SetOracle {
+                oracle: account("oracle-v2.testnet"),
+            },
+            AdminAction::SetOutcomeToken {
+                outcome_token: account("outcome-v2.testnet"),
+            },
+            AdminAction::SetCollateralToken {
+                collateral_token: account("usdc-v2.testnet"),
+            },
+        ] {
+            set_context_at("owner.testnet", 0);
+            let action_id = contract.propose_action(action);
+            set_context_at("owner.testnet", DEFAULT_TIMELOCK_DELAY_NS);
+            contract.execute_action(action_id);
+        }
+        assert!(get_logs()[1].contains("\"event\":\"collateral_token_updated\""));
+
+        let config = contract.get_config();
+        assert_eq!(config.oracle, account("oracle-v2.testnet"));
+        assert_eq!(config.usdc_token, account("usdc-v2.testnet"));
+
+        let market = contract.get_market(0).unwrap();
+        assert_eq!(market.oracle, account("oracle.testnet"));
+        assert_eq!(market.outcome_token, account("outcome.testnet"));
+        assert_eq!(market.collateral_token, account("usdc.testnet"));
+    }
+
+    #[test]
+    fn action_executes_after_delay() {
+        let mut contract = setup();
+        let action_id = contract.propose_action(set_owner("new-owner.testnet"));
+        assert!(get_logs()[0].contains("\"event\":\"admin_action_proposed\""));
+
+        let pending = contract.get_pending_actions(None, None);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].executable_at_ns, U64(DEFAULT_TIMELOCK_DELAY_NS));
+
+        set_context_at("owner.testnet", DEFAULT_TIMELOCK_DELAY_NS);
+        contract.execute_action(action_id);
+        assert_eq!(contract.owner, account("new-owner.testnet"));
+        assert!(contract.get_pending_action(action_id).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Timelock has not expired")]
+    fn action_cannot_execute_early() {
+        let mut contract = setup();
+        let action_id = contract.propose_action(set_owner("new-owner.testnet"));
+
+        set_context_at("owner.testnet", DEFAULT_TIMELOCK_DELAY_NS - HOUR_NS);
+        contract.execute_action(action_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Admin action not found")]
+    fn guardian_cancels_pending_action() {
+        let mut contract = setup();
+        let action_id = contract.propose_action(AdminAction::EmergencyWithdrawToken {
+            token: account("usdc.testnet"),
+            receiver_id: account("attacker.testnet"),
+            amount: U128(1_000 * USDC_ONE),
+            force: true,
+        });
+
+        set_context_at("guardian.testnet", HOUR_NS);
+        contract.cancel_action(action_id);
+        assert!(get_logs()[0].contains("\"event\":\"admin_action_cancelled\""));
+
+        set_context_at("owner.testnet", DEFAULT_TIMELOCK_DELAY_NS);
+        contract.execute_action(action_id);
+    }
+
+    #[test]
+    fn surplus_withdrawal_stays_pending_until_balance_check() {
+        let mut contract = setup();
+        let action_id = contract.propose_action(AdminAction::EmergencyWithdrawToken {
+            token: account("usdc.testnet"),
+            receiver_id: account("owner.testnet"),
+            amount: U128(USDC_ONE),
+            force: false,
+        });
+
+        set_context_at("owner.testnet", DEFAULT_TIMELOCK_DELAY_NS);
+        contract.execute_action(action_id);
+        assert!(contract.get_pending_action(action_id).is_some());
+        assert!(!get_logs()
+            .iter()
+            .any(|log| log.contains("\"event\":\"admin_action_executed\"")));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only guardian can cancel a pending admin action")]
+    fn approver_cannot_cancel_pending_action() {
+        let mut contract = setup_multisig();
+        set_context_at("a.testnet", 0);
+        let action_id = contract.propose_action(AdminAction::SetProtocolFeeShareBps {
+            protocol_fee_share_bps: 2_000,
+        });
+
+        set_context_at("b.testnet", HOUR_NS);
+        contract.cancel_action(action_id);
+    }
+
+    #[test]
+    fn timelock_delay_change_is_itself_timelocked() {
+        let mut contract = setup();
+        let action_id = contract.propose_action(AdminAction::SetTimelockDelay {
+            delay_ns: U64(HOUR_NS),
+        });
+        assert_eq!(contract.timelock_delay_ns, DEFAULT_TIMELOCK_DELAY_NS);
+
+        set_context_at("owner.testnet", DEFAULT_TIMELOCK_DELAY_NS);
+        contract.execute_action(action_id);
+        assert_eq!(contract.timelock_delay_ns, HOUR_NS);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only approvers can call this method")]
+    fn only_approvers_propose() {
+        let mut contract = setup();
+        set_context_at("guardian.testnet", 0);
+        contract.propose_action(set_owner("guardian.testnet"));
+    }
+
+    #[test]
+    fn action_needs_threshold_approvals() {
+        let mut contract = setup_multisig();
+        set_context_at("a.testnet", 0);
+        let action_id = contract.propose_action(AdminAction::SetProtocolFeeShareBps {
+            protocol_fee_share_bps: 2_000,
+        });
+
+        set_context_at("b.testnet", HOUR_NS / 2);
+        contract.approve_action(action_id);
+        let pending = contract.get_pending_action(action_id).unwrap();
+        assert_eq!(
+            pending.approvals,
+            vec![account("a.testnet"), account("b.testnet")]
+        );
+
+        set_context_at("c.testnet", HOUR_NS);
+        contract.execute_action(action_id);
+        assert_eq!(contract.protocol_fee_share_bps, 2_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough approvals")]
+    fn single_approver_cannot_execute() {
+        let mut contract = setup_multisig();
+        set_context_at("a.testnet", 0);
+        let action_id = contract.propose_action(set_owner("a.testnet"));
+
+        set_context_at("a.testnet", HOUR_NS);
+        contract.execute_action(action_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Admin action has expired")]
+    fn approved_action_expires() {
+        let mut contract = setup_multisig();
+        set_context_at("a.testnet", 0);
+        let action_id = contract.propose_action(AdminAction::SetOracle {
+            oracle: account("oracle-v2.testnet"),
+        });
+        set_context_at("b.testnet", HOUR_NS);
+        contract.approve_action(action_id);
+
+        set_context_at("b.testnet", 25 * HOUR_NS);
+        contract.execute_action(action_id);
+    }
+
+    #[test]
+    fn removed_approver_no_longer_counts() {
+        let mut contract = setup_multisig();
+        set_context_at("a.testnet", 0);
+        let fee_action = contract.propose_action(AdminAction::SetDefaultFeeBps {
+            default_fee_bps: 500,
+        });
+        let rotate = contract.propose_action(AdminAction::SetApprovers {
+            approvers: vec![account("b.testnet"), account("c.testnet")],
+            threshold: 2,
+        });
+        set_context_at("b.testnet", 0);
+        contract.approve_action(fee_action);
+        contract.approve_action(rotate);
+
+        set_context_at("c.testnet", HOUR_NS);
+        contract.execute_action(rotate);
+        assert_eq!(contract.get_governance().threshold, 2);
+        assert_eq!(
+            contract.current_approvals(contract.pending_actions.get(&fee_action.0).unwrap()),
+            1
+        );
+
+        contract.approve_action(fee_action);
+        contract.execute_action(fee_action);
+        assert_eq!(contract.default_fee_bps, 500);
+    }
+
+    #[test]
+    #[should_panic(expected = "Threshold must be between 1 and the number of approvers")]
+    fn threshold_cannot_exceed_approvers() {
+        let mut contract = setup_multisig();
+        set_context_at("a.testnet", 0);
+        contract.propose_action(AdminAction::SetApprovers {
+            approvers: vec![account("a.testnet")],
+            threshold: 2,
+        });
+    }
+}
@@ -1,14 +1,20 @@
 use near_sdk::json_types::U128;
-use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseResult};
+use near_sdk::{
+    env, near, require, AccountId, Gas, NearToken, Promise, PromiseOrValue, PromiseResult,
+};
 
+use market_math::{LiquidityDeposit, LiquidityWithdrawal, MathResult, SellQuote};
 use market_types::*;
 
 use crate::events::Event;
+use crate::mints::UserMint;
 use crate::{MarketContract, MarketContractExt, GAS_FOR_MINT};
 
 const GAS_FOR_SELL_BURN_CALLBACK: Gas = Gas::from_tgas(15);
 const GAS_FOR_REMOVE_LIQUIDITY_BURN_CALLBACK: Gas = Gas::from_tgas(15);
 const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_BALANCE_OF: Gas = Gas::from_tgas(5);
+const GAS_FOR_HOLDING_CALLBACK: Gas = Gas::from_tgas(40);
 
 #[near]
 impl MarketContract {
@@ -25,51 +31,56 @@ impl MarketContract {
         collateral: u128,
         min_tokens_out: u128,
         buyer: AccountId,
+        held: Option<u128>,
     ) {
         let market = self.markets.get(&market_id).expect("Market not found");
-        require!(market.status == MarketStatus::Open, "Market is not open for trading");
-
-        // Deduct fee
-        let fee = collateral * market.fee_bps as u128 / BPS_DENOMINATOR as u128;
-        let net_collateral = collateral - fee;
-
+        require!(
+            market.status == MarketStatus::Open,
+            "Market is not open for trading"
+        );
         let mut market = market.clone();
-        market.accrued_fees += fee;
-        market.total_collateral += net_collateral;
-
-        // Mint-and-swap: add net_collateral to both reserves, then swap
-        let yes_r = market.yes_reserve + net_collateral;
-        let no_r = market.no_reserve + net_collateral;
-        // k = yes_r * no_r (safe for USDC amounts up to ~18B USDC per side)
-        let k = yes_r * no_r;
-
-        let tokens_out = match outcome {
-            Outcome::Yes => {
-                // User wants YES. NO side gets the minted NO tokens.
-                let final_no = no_r + net_collateral; // NO reserve absorbs minted NO
-                let final_yes = k / final_no + 1; // round up to protect pool
-                let yes_out = yes_r - final_yes;
-                market.yes_reserve = final_yes;
-                market.no_reserve = final_no;
-                yes_out
-            }
-            Outcome::No => {
-                let final_yes = yes_r + net_collateral;
-                let final_no = k / final_yes + 1;
-                let no_out = no_r - final_no;
-                market.yes_reserve = final_yes;
-                market.no_reserve = final_no;
-                no_out
-            }
-        };
+        self.record_price_observation(&market);
+
+        // Deduct fee (may be ramped up near resolution), then mint-and-swap
+        let fee_bps = market.fee_bps_at(env::block_timestamp());
+        let quote = checked(market_math::quote_buy(
+            market.yes_reserve,
+            market.no_reserve,
+            collateral,
+            fee_bps,
+            outcome.into(),
+        ));
+        let fee = quote.fee;
+        let tokens_out = quote.tokens_out;
+        Self::check_trade_limits(
+            &market,
+            outcome,
+            collateral,
+            quote.yes_reserve,
+            quote.no_reserve,
+        );
+        self.check_holding(&market, &buyer, outcome, tokens_out, held);
+
+        self.accrue_fee(&mut market, fee);
+        market.total_collateral += quote.net_collateral;
+        market.yes_reserve = quote.yes_reserve;
+        market.no_reserve = quote.no_reserve;
+        match outcome {
+            Outcome::Yes => market.yes_supply += tokens_out,
+            Outcome::No => market.no_supply += tokens_out,
+        }
+        self.credit_owed(&market.collateral_token, collateral);
 
         require!(
             tokens_out >= min_tokens_out,
-            format!("Slippage: would receive {} but minimum is {}", tokens_out, min_tokens_out)
+            format!(
+                "Slippage: would receive {} but minimum is {}",
+                tokens_out, min_tokens_out
+            )
         );
 
         // Emit trade event
-        let total = market.yes_reserve + market.no_reserve;
+        let (yes_price, no_price) = market.prices();
         Event::Trade {
             market_id,
             trader: &buyer,
@@ -77,28 +88,30 @@ impl MarketContract {
             is_buy: true,
             collateral_amount: U128(collateral),
             token_amount: U128(tokens_out),
-            yes_price: U128(market.no_reserve * AMM_SCALE / total),
-            no_price: U128(market.yes_reserve * AMM_SCALE / total),
+            yes_price: U128(yes_price),
+            no_price: U128(no_price),
+            fee_bps,
+            fee_amount: U128(fee),
+            yes_reserve: U128(market.yes_reserve),
+            no_reserve: U128(market.no_reserve),
+            total_collateral: U128(market.total_collateral),
         }
         .emit();
 
+        let outcome_token = market.outcome_token.clone();
         self.markets.insert(market_id, market);
 
-        // Mint tokens to buyer via cross-contract call
-        Promise::new(self.outcome_token.clone())
-            .function_call(
-                "mint".to_string(),
-                near_sdk::serde_json::json!({
-                    "market_id": market_id,
-                    "outcome": outcome,
-                    "account_id": buyer,
-                    "amount": U128(tokens_out),
-                })
-                .to_string()
-                .into_bytes(),
-                NearToken::from_yoctonear(0),
-                GAS_FOR_MINT,
-            );
+        // Mint tokens to buyer; if that fails they stay claimable
+        self.mint_to(
+            outcome_token,
+            &buyer,
+            vec![UserMint {
+                market_id,
+                outcome,
+                amount: U128(tokens_out),
+            }],
+            GAS_FOR_MINT,
+        );
     }
 
     // ── Sell Flow ──────────────────────────────────────────────────────
@@ -114,49 +127,57 @@ impl MarketContract {
         tokens_in: U128,
         min_collateral_out: U128,
     ) {
+        // Sells are never gated, so holders can always exit a market
         let seller = env::predecessor_account_id();
         let tokens_in = tokens_in.0;
         let min_collateral_out = min_collateral_out.0;
 
         let market = self.markets.get(&market_id).expect("Market not found");
-        require!(market.status == MarketStatus::Open, "Market is not open for trading");
+        require!(
+            market.status == MarketStatus::Open,
+            "Market is not open for trading"
+        );
         let mut market = market.clone();
-
-        // Swap: add tokens_in to the outcome's reserve, extract from opposite
-        let (collateral_before_fee, new_yes, new_no) = match outcome {
-            Outcome::Yes => {
-                let k = market.yes_reserve * market.no_reserve;
-                let new_yes = market.yes_reserve + tokens_in;
-                let new_no = k / new_yes + 1;
-                let no_extracted = market.no_reserve - new_no;
-                // Burn matched pairs: min(tokens_in, no_extracted) pairs
-                let pairs = tokens_in.min(no_extracted);
-                (pairs, new_yes - pairs, new_no)
-            }
-            Outcome::No => {
-                let k = market.yes_reserve * market.no_reserve;
-                let new_no = market.no_reserve + tokens_in;
-                let new_yes = k / new_no + 1;
-                let yes_extracted = market.yes_reserve - new_yes;
-                let pairs = tokens_in.min(yes_extracted);
-                (pairs, new_yes, new_no - pairs)
-            }
-        };
-
-        let fee = collateral_before_fee * market.fee_bps as u128 / BPS_DENOMINATOR as u128;
-        let collateral_out = collateral_before_fee - fee;
+        self.record_price_observation(&market);
+
+        // Swap: add tokens_in to the outcome's reserve, extract from opposite,
+        // burn matched pairs for collateral
+        let fee_bps = market.fee_bps_at(env::block_timestamp());
+        let quote = checked(market_math::quote_sell(
+            market.yes_reserve,
+            market.no_reserve,
+            tokens_in,
+            fee_bps,
+            outcome.into(),
+        ));
+        let SellQuote {
+            collateral_before_fee,
+            fee,
+            collateral_out,
+            yes_reserve: new_yes,
+            no_reserve: new_no,
+        } = quote;
 
         require!(
             collateral_out >= min_collateral_out,
-            format!("Slippage: would receive {} but minimum is {}", collateral_out, min_collateral_out)
+            format!(
+                "Slippage: would receive {} but minimum is {}",
+                collateral_out, min_collateral_out
+            )
         );
+        Self::check_trade_limits(&market, outcome, collateral_before_fee, new_yes, new_no);
 
         market.yes_reserve = new_yes;
         market.no_reserve = new_no;
-        market.accrued_fees += fee;
+        self.accrue_fee(&mut market, fee);
         market.total_collateral -= collateral_before_fee;
+        match outcome {
+            Outcome::Yes => market.yes_supply -= tokens_in,
+            Outcome::No => market.no_supply -= tokens_in,
+        }
+        self.debit_owed(&market.collateral_token, collateral_out);
 
-        let total = market.yes_reserve + market.no_reserve;
+        let (yes_price, no_price) = market.prices();
         Event::Trade {
             market_id,
             trader: &seller,
@@ -164,15 +185,22 @@ impl MarketContract {
             is_buy: false,
             collateral_amount: U128(collateral_out),
             token_amount: U128(tokens_in),
-            yes_price: U128(if total > 0 { market.no_reserve * AMM_SCALE / total } else { AMM_SCALE / 2 }),
-            no_price: U128(if total > 0 { market.yes_reserve * AMM_SCALE / total } else { AMM_SCALE / 2 }),
+            yes_price: U128(yes_price),
+            no_price: U128(no_price),
+            fee_bps,
+            fee_amount: U128(fee),
+            yes_reserve: U128(market.yes_reserve),
+            no_reserve: U128(market.no_reserve),
+            total_collateral: U128(market.total_collateral),
         }
         .emit();
 
+        let outcome_token = market.outcome_token.clone();
+        let collateral_token = market.collateral_token.clone();
         self.markets.insert(market_id, market);
 
         // Burn seller's tokens via cross-contract call, then transfer USDC
-        Promise::new(self.outcome_token.clone())
+        Promise::new(outcome_token.clone())
             .function_call(
                 "burn".to_string(),
                 near_sdk::serde_json::json!({
@@ -187,23 +215,23 @@ impl MarketContract {
                 GAS_FOR_MINT,
             )
             .then(
-                Promise::new(env::current_account_id())
-                    .function_call(
-                        "on_sell_burn_complete".to_string(),
-                        near_sdk::serde_json::json!({
-                            "seller": seller,
-                            "amount": U128(collateral_out),
-                        })
-                        .to_string()
-                        .into_bytes(),
-                        NearToken::from_yoctonear(0),
-                        GAS_FOR_SELL_BURN_CALLBACK,
-                    )
+                Promise::new(env::current_account_id()).function_call(
+                    "on_sell_burn_complete".to_string(),
+                    near_sdk::serde_json::json!({
+                        "seller": seller,
+                        "amount": U128(collateral_out),
+                        "token": collateral_token,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_SELL_BURN_CALLBACK,
+                ),
             );
     }
 
     #[private]
-    pub fn on_sell_burn_complete(&mut self, seller: AccountId, amount: U128) {
+    pub fn on_sell_burn_complete(&mut self, seller: AccountId, amount: U128, token: AccountId) {
         require!(
             env::promise_results_count() == 1,
             "Expected one promise result"
@@ -211,7 +239,7 @@ impl MarketContract {
 
         match env::promise_result(0) {
             PromiseResult::Successful(_) => {
-                Promise::new(self.usdc_token.clone()).function_call(
+                Promise::new(token).function_call(
                     "ft_transfer".to_string(),
                     near_sdk::serde_json::json!({
                         "receiver_id": seller,
@@ -240,23 +268,30 @@ impl MarketContract {
         let market = self.markets.get(&market_id).expect("Market not found");
         require!(market.status == MarketStatus::Open, "Market is not open");
         let mut market = market.clone();
-
-        // Calculate LP shares proportional to existing reserves
-        let lp_shares = if market.total_lp_shares == 0 {
-            amount
-        } else {
-            amount * market.total_lp_shares / market.total_collateral
-        };
+        self.record_price_observation(&market);
+
+        // LP shares and reserve additions proportional to existing collateral
+        let LiquidityDeposit {
+            lp_shares,
+            yes_add,
+            no_add,
+        } = checked(market_math::quote_add_liquidity(
+            amount,
+            market.yes_reserve,
+            market.no_reserve,
+            market.total_collateral,
+            market.total_lp_shares,
+        ));
 
         require!(lp_shares > 0, "Liquidity too small");
 
-        // Add to both reserves proportionally
-        let yes_add = amount * market.yes_reserve / market.total_collateral;
-        let no_add = amount * market.no_reserve / market.total_collateral;
         market.yes_reserve += yes_add;
         market.no_reserve += no_add;
         market.total_collateral += amount;
         market.total_lp_shares += lp_shares;
+        market.yes_supply += yes_add;
+        market.no_supply += no_add;
+        self.credit_owed(&market.collateral_token, amount);
 
         // Track LP position
         let lp_key = Self::lp_key(market_id, &provider);
@@ -268,14 +303,19 @@ impl MarketContract {
             provider: &provider,
             amount: U128(amount),
             lp_shares: U128(lp_shares),
+            yes_reserve: U128(market.yes_reserve),
+            no_reserve: U128(market.no_reserve),
+            total_collateral: U128(market.total_collateral),
+            total_lp_shares: U128(market.total_lp_shares),
         }
         .emit();
 
+        let outcome_token = market.outcome_token.clone();
         self.markets.insert(market_id, market);
 
         // Mint YES+NO tokens to contract to back reserves
         let contract_id = env::current_account_id();
-        Promise::new(self.outcome_token.clone())
+        Promise::new(outcome_token.clone())
             .function_call(
                 "mint".to_string(),
                 near_sdk::serde_json::json!({
@@ -290,20 +330,19 @@ impl MarketContract {
                 GAS_FOR_MINT,
             )
             .and(
-                Promise::new(self.outcome_token.clone())
-                    .function_call(
-                        "mint".to_string(),
-                        near_sdk::serde_json::json!({
-                            "market_id": market_id,
-                            "outcome": Outcome::No,
-                            "account_id": contract_id,
-                            "amount": U128(no_add),
-                        })
-                        .to_string()
-                        .into_bytes(),
-                        NearToken::from_yoctonear(0),
-                        GAS_FOR_MINT,
-                    )
+                Promise::new(outcome_token).function_call(
+                    "mint".to_string(),
+                    near_sdk::serde_json::json!({
+                        "market_id": market_id,
+                        "outcome": Outcome::No,
+                        "account_id": contract_id,
+                        "amount": U128(no_add),
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_MINT,
+                ),
             );
     }
 
@@ -320,16 +359,31 @@ impl MarketContract {
         let market = self.markets.get(&market_id).expect("Market not found");
         require!(market.status == MarketStatus::Open, "Market is not open");
         let mut market = market.clone();
-
-        // Calculate proportional withdrawal
-        let collateral_out = shares * market.total_collateral / market.total_lp_shares;
-        let yes_remove = shares * market.yes_reserve / market.total_lp_shares;
-        let no_remove = shares * market.no_reserve / market.total_lp_shares;
+        self.record_price_observation(&market);
+
+        // Proportional withdrawal, including the LP share of accrued fees
+        let LiquidityWithdrawal {
+            collateral_out,
+            fees_out,
+            yes_remove,
+            no_remove,
+        } = checked(market_math::quote_remove_liquidity(
+            shares,
+            market.total_lp_shares,
+            market.total_collateral,
+            market.accrued_fees,
+            market.yes_reserve,
+            market.no_reserve,
+        ));
 
         market.yes_reserve -= yes_remove;
         market.no_reserve -= no_remove;
         market.total_collateral -= collateral_out;
+        market.accrued_fees -= fees_out;
         market.total_lp_shares -= shares;
+        market.yes_supply -= yes_remove;
+        market.no_supply -= no_remove;
+        self.debit_owed(&market.collateral_token, collateral_out + fees_out);
 
         self.lp_positions.insert(lp_key, lp_balance - shares);
 
@@ -338,14 +392,21 @@ impl MarketContract {
             provider: &provider,
             amount: U128(collateral_out),
             lp_shares: U128(shares),
+            fees_out: U128(fees_out),
+            yes_reserve: U128(market.yes_reserve),
+            no_reserve: U128(market.no_reserve),
+            total_collateral: U128(market.total_collateral),
+            total_lp_shares: U128(market.total_lp_shares),
         }
         .emit();
 
+        let outcome_token = market.outcome_token.clone();
+        let collateral_token = market.collateral_token.clone();
         self.markets.insert(market_id, market);
 
         // Burn contract's tokens and transfer USDC to provider
         let contract_id = env::current_account_id();
-        Promise::new(self.outcome_token.clone())
+        Promise::new(outcome_token.clone())
             .function_call(
                 "burn".to_string(),
                 near_sdk::serde_json::json!({
@@ -360,39 +421,43 @@ impl MarketContract {
                 GAS_FOR_MINT,
             )
             .and(
-                Promise::new(self.outcome_token.clone())
-                    .function_call(
-                        "burn".to_string(),
-                        near_sdk::serde_json::json!({
-                            "market_id": market_id,
-                            "outcome": Outcome::No,
-                            "account_id": contract_id,
-                            "amount": U128(no_remove),
-                        })
-                        .to_string()
-                        .into_bytes(),
-                        NearToken::from_yoctonear(0),
-                        GAS_FOR_MINT,
-                    )
+                Promise::new(outcome_token).function_call(
+                    "burn".to_string(),
+                    near_sdk::serde_json::json!({
+                        "market_id": market_id,
+                        "outcome": Outcome::No,
+                        "account_id": contract_id,
+                        "amount": U128(no_remove),
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_MINT,
+                ),
             )
             .then(
-                Promise::new(env::current_account_id())
-                    .function_call(
-                        "on_remove_liquidity_burn_complete".to_string(),
-                        near_sdk::serde_json::json!({
-                            "provider": provider,
-                            "amount": U128(collateral_out),
-                        })
-                        .to_string()
-                        .into_bytes(),
-                        NearToken::from_yoctonear(0),
-                        GAS_FOR_REMOVE_LIQUIDITY_BURN_CALLBACK,
-                    )
+                Promise::new(env::current_account_id()).function_call(
+                    "on_remove_liquidity_burn_complete".to_string(),
+                    near_sdk::serde_json::json!({
+                        "provider": provider,
+                        "amount": U128(collateral_out + fees_out),
+                        "token": collateral_token,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_REMOVE_LIQUIDITY_BURN_CALLBACK,
+                ),
             );
     }
 
     #[private]
-    pub fn on_remove_liquidity_burn_complete(&mut self, provider: AccountId, amount: U128) {
+    pub fn on_remove_liquidity_burn_complete(
+        &mut self,
+        provider: AccountId,
+        amount: U128,
+        token: AccountId,
+    ) {
         require!(
             env::promise_results_count() == 2,
             "Expected two promise results"
@@ -404,7 +469,7 @@ impl MarketContract {
             env::panic_str("Token burn failed, cannot remove liquidity");
         }
 
-        Promise::new(self.usdc_token.clone()).function_call(
+        Promise::new(token).function_call(
             "ft_transfer".to_string(),
             near_sdk::serde_json::json!({
                 "receiver_id": provider,
@@ -417,6 +482,38 @@ impl MarketContract {
         );
     }
 
+    // ── Trade Limits ───────────────────────────────────────────────────
+
+    /// Run a held-back buy once the buyer's outcome token balance is known.
+    /// Panics past the holding cap, so an `ft_transfer_call` deposit is refunded.
+    #[private]
+    pub fn on_holding_checked(
+        &mut self,
+        market_id: MarketId,
+        buyer: AccountId,
+        outcome: Outcome,
+        collateral: U128,
+        min_tokens_out: U128,
+    ) -> U128 {
+        let held = match env::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                near_sdk::serde_json::from_slice::<U128>(&bytes)
+                    .expect("Invalid outcome token balance")
+                    .0
+            }
+            _ => env::panic_str("Failed to check outcome token balance"),
+        };
+        self.internal_buy(
+            market_id,
+            outcome,
+            collateral.0,
+            min_tokens_out.0,
+            buyer,
+            Some(held),
+        );
+        U128(0)
+    }
+
     // ── Helpers ────────────────────────────────────────────────────────
 
     pub(crate) fn lp_key(market_id: MarketId, account: &AccountId) -> Vec<u8> {
@@ -427,78 +524,139 @@ impl MarketContract {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use near_sdk::store::LookupMap;
-    use near_sdk::test_utils::VMContextBuilder;
-    use near_sdk::testing_env;
-
-    fn account(id: &str) -> AccountId {
-        id.parse().unwrap()
-    }
+/// Unwrap an AMM math result, aborting the call on overflow or an exhausted pool.
+pub(crate) fn checked<T>(result: MathResult<T>) -> T {
+    result.unwrap_or_else(|e| env::panic_str(e.as_str()))
+}
 
-    fn set_context_with_results(
-        predecessor: &str,
-        current: &str,
-        promise_results: Vec<PromiseResult>,
+impl MarketContract {
+    /// Enforce the market's trade size and price impact caps for a trade that
+    /// moves the pool to the given reserves.
+    fn check_trade_limits(
+        market: &Market,
+        outcome: Outcome,
+        collateral: u128,
+        yes_reserve: u128,
+        no_reserve: u128,
     ) {
-        let mut builder = VMContextBuilder::new();
-        builder
-            .predecessor_account_id(account(predecessor))
-            .current_account_id(account(current));
-
-        testing_env!(
-            builder.build(),
-            near_sdk::test_vm_config(),
-            near_sdk::RuntimeFeesConfig::test(),
-            Default::default(),
-            promise_results
-        );
+        let limits = market.limits;
+        if let Some(max) = limits.max_trade_collateral {
+            require!(
+                collateral <= max.0,
+                format!(
+                    "Trade of {} exceeds max trade collateral of {}",
+                    collateral, max.0
+                )
+            );
+        }
+        if let Some(max) = limits.max_price_impact_bps {
+            let price = |(yes, no): (u128, u128)| match outcome {
+                Outcome::Yes => yes,
+                Outcome::No => no,
+            };
+            let before = price(market.prices());
+            let after = price(market_math::spot_prices(yes_reserve, no_reserve));
+            let impact = checked(market_math::price_impact_bps(before, after));
+            require!(
+                impact <= max as u128,
+                format!("Price impact of {} bps exceeds max of {} bps", impact, max)
+            );
+        }
     }
 
-    fn test_contract() -> MarketContract {
-        MarketContract {
-            owner: account("owner.testnet"),
-            usdc_token: account("usdc.testnet"),
-            outcome_token: account("outcome.testnet"),
-            oracle: account("oracle.testnet"),
-            markets: LookupMap::new(b"m"),
-            market_count: 0,
-            lp_positions: LookupMap::new(b"l"),
-            assertion_to_market: LookupMap::new(b"a"),
+    /// Buy for `buyer`. Markets with a holding cap first read the buyer's
+    /// outcome token balance and buy in `on_holding_checked`.
+    pub(crate) fn internal_capped_buy(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        collateral: U128,
+        min_tokens_out: U128,
+        buyer: AccountId,
+    ) -> PromiseOrValue<U128> {
+        let market = self.markets.get(&market_id).expect("Market not found");
+        if market.limits.max_holding.is_none() {
+            self.internal_buy(
+                market_id,
+                outcome,
+                collateral.0,
+                min_tokens_out.0,
+                buyer,
+                None,
+            );
+            return PromiseOrValue::Value(U128(0));
         }
+
+        PromiseOrValue::Promise(
+            Promise::new(market.outcome_token.clone())
+                .function_call(
+                    "balance_of".to_string(),
+                    near_sdk::serde_json::json!({
+                        "market_id": market_id,
+                        "outcome": outcome,
+                        "account_id": buyer,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_BALANCE_OF,
+                )
+                .then(
+                    Promise::new(env::current_account_id()).function_call(
+                        "on_holding_checked".to_string(),
+                        near_sdk::serde_json::json!({
+                            "market_id": market_id,
+                            "buyer": buyer,
+                            "outcome": outcome,
+                            "collateral": collateral,
+                            "min_tokens_out": min_tokens_out,
+                        })
+                        .to_string()
+                        .into_bytes(),
+                        NearToken::from_yoctonear(0),
+                        GAS_FOR_HOLDING_CALLBACK,
+                    ),
+                ),
+        )
     }
 
-    fn base_market(id: u64, creator: &str) -> Market {
-        Market {
-            id,
-            question: "Will test pass?".to_string(),
-            description: "test".to_string(),
-            creator: account(creator),
-            resolution_time_ns: 999_999_999_999,
-            status: MarketStatus::Open,
-            outcome: None,
-            yes_reserve: 50 * USDC_ONE,
-            no_reserve: 50 * USDC_ONE,
-            total_lp_shares: 100 * USDC_ONE,
-            total_collateral: 100 * USDC_ONE,
-            fee_bps: DEFAULT_FEE_BPS,
-            accrued_fees: 0,
-            assertion_id: None,
-            asserted_outcome: None,
-            resolver: None,
-            disputer: None,
-            assertion_submitted_at_ns: None,
-            assertion_expires_at_ns: None,
-        }
+    /// Enforce the market's holding cap. `held` is the buyer's outcome token
+    /// balance; tokens it is still owed or being minted count towards the cap.
+    fn check_holding(
+        &self,
+        market: &Market,
+        account_id: &AccountId,
+        outcome: Outcome,
+        tokens_out: u128,
+        held: Option<u128>,
+    ) {
+        let Some(max) = market.limits.max_holding else {
+            return;
+        };
+        let held = held.unwrap_or_else(|| env::panic_str("Buyer's balance was not checked"));
+        let holding = held
+            + self.get_unminted(market.id, outcome, account_id.clone()).0
+            + self.pending_mint(market.id, account_id, outcome)
+            + tokens_out;
+        require!(
+            holding <= max.0,
+            format!(
+                "Holding of {} would exceed max holding of {}",
+                holding, max.0
+            )
+        );
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
 
     #[test]
     fn sell_updates_market_state_and_fees() {
-        let mut contract = test_contract();
-        contract.markets.insert(0, base_market(0, "creator.testnet"));
-        set_context_with_results("seller.testnet", "market.testnet", vec![]);
+        let mut contract = ContractBuilder::new().market().build();
+        set_context("seller.testnet", vec![]);
 
         contract.sell(0, Outcome::Yes, U128(10 * USDC_ONE), U128(0));
 
@@ -507,13 +665,31 @@ mod tests {
         assert!(market.accrued_fees > 0);
     }
 
+    #[test]
+    fn trade_event_carries_reserves_and_fee() {
+        let mut contract = ContractBuilder::new().market().build();
+        set_context("seller.testnet", vec![]);
+
+        contract.sell(0, Outcome::Yes, U128(10 * USDC_ONE), U128(0));
+
+        let market = contract.markets.get(&0).unwrap();
+        let logs = near_sdk::test_utils::get_logs();
+        let trade = logs
+            .iter()
+            .find(|log| log.contains("\"event\":\"trade\""))
+            .expect("trade event emitted");
+        assert!(trade.contains(&format!("\"yes_reserve\":\"{}\"", market.yes_reserve)));
+        assert!(trade.contains(&format!("\"no_reserve\":\"{}\"", market.no_reserve)));
+        assert!(trade.contains(&format!("\"fee_amount\":\"{}\"", market.accrued_fees)));
+        assert!(logs
+            .iter()
+            .any(|log| log.contains("\"event\":\"fees_accrued\"")));
+    }
+
     #[test]
     fn remove_liquidity_updates_market_and_lp_position() {
-        let mut contract = test_contract();
-        contract.markets.insert(0, base_market(0, "lp.testnet"));
-        let key = MarketContract::lp_key(0, &account("lp.testnet"));
-        contract.lp_positions.insert(key, 100 * USDC_ONE);
-        set_context_with_results("lp.testnet", "market.testnet", vec![]);
+        let mut contract = ContractBuilder::new().market().build();
+        set_context("creator.testnet", vec![]);
 
         contract.remove_liquidity(0, U128(20 * USDC_ONE));
 
@@ -524,34 +700,56 @@ mod tests {
 
     #[test]
     fn on_sell_burn_complete_succeeds_on_successful_burn() {
-        let mut contract = test_contract();
-        set_context_with_results(
-            "market.testnet",
-            "market.testnet",
-            vec![PromiseResult::Successful(vec![])],
-        );
+        let mut contract = ContractBuilder::new().build();
+        set_context("market.testnet", vec![PromiseResult::Successful(vec![])]);
 
-        contract.on_sell_burn_complete(account("seller.testnet"), U128(10 * USDC_ONE));
+        contract.on_sell_burn_complete(
+            account("seller.testnet"),
+            U128(10 * USDC_ONE),
+            account("usdc.testnet"),
+        );
     }
 
     #[test]
     #[should_panic(expected = "Token burn failed, cannot settle sell")]
     fn on_sell_burn_complete_panics_on_failed_burn() {
-        let mut contract = test_contract();
-        set_context_with_results(
-            "market.testnet",
-            "market.testnet",
-            vec![PromiseResult::Failed],
+        let mut contract = ContractBuilder::new().build();
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+
+        contract.on_sell_burn_complete(
+            account("seller.testnet"),
+            U128(10 * USDC_ONE),
+            account("usdc.testnet"),
         );
+    }
 
-        contract.on_sell_burn_complete(account("seller.testnet"), U128(10 * USDC_ONE));
+    #[test]
+    fn failed_mint_is_kept_for_claiming() {
+        let mut contract = ContractBuilder::new().market().build();
+        let mint = UserMint {
+            market_id: 0,
+            outcome: Outcome::Yes,
+            amount: U128(7 * USDC_ONE),
+        };
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+        contract.on_mint_to(account("buyer.testnet"), vec![mint.clone(), mint]);
+        assert_eq!(
+            contract.get_unminted(0, Outcome::Yes, account("buyer.testnet")),
+            U128(14 * USDC_ONE)
+        );
+
+        set_context("buyer.testnet", vec![]);
+        contract.claim_unminted(0, Outcome::Yes);
+        assert_eq!(
+            contract.get_unminted(0, Outcome::Yes, account("buyer.testnet")),
+            U128(0)
+        );
     }
 
     #[test]
     fn on_remove_liquidity_burn_complete_succeeds_when_both_burns_succeed() {
-        let mut contract = test_contract();
-        set_context_with_results(
-            "market.testnet",
+        let mut contract = ContractBuilder::new().build();
+        set_context(
             "market.testnet",
             vec![
                 PromiseResult::Successful(vec![]),
@@ -559,19 +757,150 @@ mod tests {
             ],
         );
 
-        contract.on_remove_liquidity_burn_complete(account("lp.testnet"), U128(5 * USDC_ONE));
+        contract.on_remove_liquidity_burn_complete(
+            account("lp.testnet"),
+            U128(5 * USDC_ONE),
+            account("usdc.testnet"),
+        );
     }
 
     #[test]
     #[should_panic(expected = "Token burn failed, cannot remove liquidity")]
     fn on_remove_liquidity_burn_complete_panics_if_any_burn_fails() {
-        let mut contract = test_contract();
-        set_context_with_results(
-            "market.testnet",
+        let mut contract = ContractBuilder::new().build();
+        set_context(
             "market.testnet",
             vec![PromiseResult::Successful(vec![]), PromiseResult::Failed],
         );
 
-        contract.on_remove_liquidity_burn_complete(account("lp.testnet"), U128(5 * USDC_ONE));
+        contract.on_remove_liquidity_burn_complete(
+            account("lp.testnet"),
+            U128(5 * USDC_ONE),
+            account("usdc.testnet"),
+        );
+    }
+
+    fn limited_market(limits: TradeLimits) -> MarketContract {
+        ContractBuilder::new()
+            .market_with(CreateMarketArgs {
+                limits: Some(limits),
+                ..market_args()
+            })
+            .build()
+    }
+
+    #[test]
+    #[should_panic(expected = "Trade of 20000000 exceeds max trade collateral of 10000000")]
+    fn buy_rejected_above_max_trade_collateral() {
+        let mut contract = limited_market(TradeLimits {
+            max_trade_collateral: Some(U128(10 * USDC_ONE)),
+            ..Default::default()
+        });
+        set_context("usdc.testnet", vec![]);
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            20 * USDC_ONE,
+            0,
+            account("whale.testnet"),
+            None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds max of 100 bps")]
+    fn buy_rejected_above_max_price_impact() {
+        let mut contract = limited_market(TradeLimits {
+            max_price_impact_bps: Some(100),
+            ..Default::default()
+        });
+        set_context("usdc.testnet", vec![]);
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            0,
+            account("whale.testnet"),
+            None,
+        );
+    }
+
+    fn held_result(balance: u128) -> Vec<PromiseResult> {
+        vec![PromiseResult::Successful(
+            near_sdk::serde_json::to_vec(&U128(balance)).unwrap(),
+        )]
+    }
+
+    #[test]
+    fn capped_buy_waits_for_balance_check() {
+        let mut contract = limited_market(TradeLimits {
+            max_holding: Some(U128(20 * USDC_ONE)),
+            ..Default::default()
+        });
+        set_context("usdc.testnet", vec![]);
+        let result = contract.internal_capped_buy(
+            0,
+            Outcome::Yes,
+            U128(5 * USDC_ONE),
+            U128(0),
+            account("whale.testnet"),
+        );
+        assert!(matches!(result, PromiseOrValue::Promise(_)));
+        assert_eq!(contract.markets.get(&0).unwrap().yes_supply, 50 * USDC_ONE);
+
+        set_context("market.testnet", held_result(0));
+        contract.on_holding_checked(
+            0,
+            account("whale.testnet"),
+            Outcome::Yes,
+            U128(5 * USDC_ONE),
+            U128(0),
+        );
+        assert!(contract.markets.get(&0).unwrap().yes_supply > 50 * USDC_ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "would exceed max holding of 8000000")]
+    fn holding_cap_counts_tokens_already_held() {
+        let mut contract = limited_market(TradeLimits {
+            max_holding: Some(U128(8 * USDC_ONE)),
+            ..Default::default()
+        });
+        set_context("market.testnet", held_result(7 * USDC_ONE));
+        contract.on_holding_checked(
+            0,
+            account("whale.testnet"),
+            Outcome::Yes,
+            U128(2 * USDC_ONE),
+            U128(0),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "would exceed max holding of 8000000")]
+    fn holding_cap_counts_tokens_being_minted() {
+        let mut contract = limited_market(TradeLimits {
+            max_holding: Some(U128(8 * USDC_ONE)),
+            ..Default::default()
+        });
+        set_context("market.testnet", held_result(0));
+        contract.on_holding_checked(
+            0,
+            account("whale.testnet"),
+            Outcome::Yes,
+            U128(5 * USDC_ONE),
+            U128(0),
+        );
+        assert!(contract.pending_mint(0, &account("whale.testnet"), Outcome::Yes) > 0);
+
+        // A second buy read the same balance before the first mint landed
+        set_context("market.testnet", held_result(0));
+        contract.on_holding_checked(
+            0,
+            account("whale.testnet"),
+            Outcome::Yes,
+            U128(5 * USDC_ONE),
+            U128(0),
+        );
     }
 }
@@ -0,0 +1,389 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseResult};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt};
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_CREATOR_PAYOUT_CALLBACK: Gas = Gas::from_tgas(10);
+
+#[near]
+impl MarketContract {
+    // ── Creation Bond ──────────────────────────────────────────────────
+
+    /// Refund a settled market's creation bond to its creator. Callable by anyone.
+    pub fn claim_creation_bond(&mut self, market_id: MarketId) -> Promise {
+        let mut market = self
+            .markets
+            .get(&market_id)
+            .expect("Market not found")
+            .clone();
+        require!(
+            market.status == MarketStatus::Settled,
+            "Market is not settled"
+        );
+        require!(
+            market.bond_status == BondStatus::Held,
+            "No creation bond to refund"
+        );
+
+        let amount = market.creation_bond;
+        market.bond_status = BondStatus::Refunded;
+        let creator = market.creator.clone();
+        let token = market.collateral_token.clone();
+        self.markets.insert(market_id, market);
+        self.debit_owed(&token, amount);
+
+        self.internal_creator_payout(market_id, token, creator, amount, true)
+    }
+
+    /// Slash the creation bond of a market with an invalid or ambiguous question.
+    /// The bond goes to the treasury and the reason is kept on the market.
+    /// Curators only, until the market settles.
+    pub fn slash_creation_bond(&mut self, market_id: MarketId, reason: Option<String>) {
+        let curator = self.assert_curator();
+        let mut market = self
+            .markets
+            .get(&market_id)
+            .expect("Market not found")
+            .clone();
+        require!(
+            market.status != MarketStatus::Settled,
+            "Cannot slash the bond of a settled market"
+        );
+        require!(
+            market.bond_status == BondStatus::Held,
+            "No creation bond to slash"
+        );
+
+        let amount = market.creation_bond;
+        market.bond_status = BondStatus::Slashed;
+        market.bond_slash_reason = reason;
+        // Stays owed, now as part of the treasury balance
+        let token = market.collateral_token.clone();
+        let balance = self.protocol_fee_balance(&token) + amount;
+        let accrued = self.protocol_fees_accrued(&token) + amount;
+        self.protocol_fee_balances.insert(token.clone(), balance);
+        self.protocol_fees_accrued.insert(token, accrued);
+
+        Event::CreationBondSlashed {
+            market_id,
+            curator: &curator,
+            amount: U128(amount),
+            reason: market.bond_slash_reason.as_deref(),
+        }
+        .emit();
+
+        self.markets.insert(market_id, market);
+    }
+
+    // ── Creator Fees ───────────────────────────────────────────────────
+
+    /// Withdraw the creator's share of trading fees. Creator only.
+    pub fn claim_creator_fees(&mut self, market_id: MarketId) -> Promise {
+        let mut market = self
+            .markets
+            .get(&market_id)
+            .expect("Market not found")
+            .clone();
+        require!(
+            env::predecessor_account_id() == market.creator,
+            "Only the market creator can claim creator fees"
+        );
+        let amount = market.creator_fees;
+        require!(amount > 0, "No creator fees to claim");
+
+        // Debit up front so a concurrent call cannot claim the same fees twice
+        market.creator_fees = 0;
+        let creator = market.creator.clone();
+        let token = market.collateral_token.clone();
+        self.markets.insert(market_id, market);
+        self.debit_owed(&token, amount);
+
+        self.internal_creator_payout(market_id, token, creator, amount, false)
+    }
+
+    #[private]
+    pub fn on_creator_payout(
+        &mut self,
+        market_id: MarketId,
+        token: AccountId,
+        amount: U128,
+        is_bond: bool,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            "Expected one promise result"
+        );
+
+        let mut market = self
+            .markets
+            .get(&market_id)
+            .expect("Market not found")
+            .clone();
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                if is_bond {
+                    Event::CreationBondRefunded {
+                        market_id,
+                        creator: &market.creator,
+                        amount,
+                    }
+                    .emit();
+                } else {
+                    Event::CreatorFeesClaimed {
+                        market_id,
+                        creator: &market.creator,
+                        amount,
+                    }
+                    .emit();
+                }
+            }
+            _ => {
+                // Transfer failed — restore so the creator can claim again
+                if is_bond {
+                    market.bond_status = BondStatus::Held;
+                } else {
+                    market.creator_fees += amount.0;
+                }
+                self.markets.insert(market_id, market);
+                self.credit_owed(&token, amount.0);
+                env::log_str(&format!(
+                    "Creator payout of {} for market {market_id} failed — balance restored",
+                    amount.0
+                ));
+            }
+        }
+    }
+}
+
+impl MarketContract {
+    fn internal_creator_payout(
+        &self,
+        market_id: MarketId,
+        token: AccountId,
+        creator: AccountId,
+        amount: u128,
+        is_bond: bool,
+    ) -> Promise {
+        Promise::new(token.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "receiver_id": creator,
+                    "amount": U128(amount),
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(1),
+                GAS_FOR_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_creator_payout".to_string(),
+                    near_sdk::serde_json::json!({
+                        "market_id": market_id,
+                        "token": token,
+                        "amount": U128(amount),
+                        "is_bond": is_bond,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_CREATOR_PAYOUT_CALLBACK,
+                ),
+            )
+    }
+
+    // ── Creator Config ─────────────────────────────────────────────────
+
+    /// Set the bond new market creators post. Applied through an admin action;
+    /// existing markets keep the bond they were created with.
+    pub(crate) fn set_creation_bond(&mut self, amount: U128) {
+        self.creation_bond = amount.0;
+
+        Event::CreationBondUpdated { amount }.emit();
+    }
+
+    /// Set the creator share of trading fees for new markets. Applied through an admin action.
+    pub(crate) fn set_creator_fee_share_bps(&mut self, creator_fee_share_bps: u16) {
+        require!(
+            creator_fee_share_bps <= MAX_CREATOR_FEE_SHARE_BPS,
+            format!(
+                "Creator fee share cannot exceed {} bps",
+                MAX_CREATOR_FEE_SHARE_BPS
+            )
+        );
+        self.creator_fee_share_bps = creator_fee_share_bps;
+
+        Event::CreatorFeeShareUpdated {
+            creator_fee_share_bps,
+        }
+        .emit();
+    }
+
+    /// Replace the accounts allowed to slash creation bonds. Applied through an admin action.
+    pub(crate) fn set_curators(&mut self, curators: Vec<AccountId>) {
+        let mut curators = curators;
+        curators.sort_unstable();
+        curators.dedup();
+        self.curators = curators;
+
+        Event::CuratorsUpdated {
+            curators: &self.curators,
+        }
+        .emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::test_utils::get_logs;
+
+    const BOND: u128 = 5 * USDC_ONE;
+
+    fn test_contract() -> ContractBuilder {
+        ContractBuilder::new()
+            .creation_bond(BOND)
+            .creator_fee_share_bps(1_000)
+            .curators(&["curator.testnet"])
+    }
+
+    #[test]
+    fn bond_is_taken_from_the_deposit() {
+        let contract = test_contract().market().build();
+
+        let market = contract.get_market(0).unwrap();
+        assert_eq!(market.total_collateral, U128(100 * USDC_ONE));
+        assert_eq!(market.creation_bond, U128(BOND));
+        assert_eq!(market.bond_status, BondStatus::Held);
+        assert_eq!(market.creator_fee_share_bps, 1_000);
+        assert_eq!(
+            contract.get_total_owed(account("usdc.testnet")),
+            U128(100 * USDC_ONE + BOND)
+        );
+        assert!(contract.check_invariants(0).ok);
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum initial liquidity is 10 USDC")]
+    fn deposit_must_cover_bond_and_minimum_liquidity() {
+        let mut contract = test_contract().build();
+        create_market(
+            &mut contract,
+            market_args(),
+            MIN_INITIAL_LIQUIDITY + BOND - 1,
+        );
+    }
+
+    #[test]
+    fn bond_refunded_after_settlement_and_restored_on_failure() {
+        let mut contract = test_contract().market().build();
+        settle(&mut contract, 0, Outcome::Yes);
+
+        set_context("anyone.testnet", vec![]);
+        contract.claim_creation_bond(0);
+        assert_eq!(
+            contract.markets.get(&0).unwrap().bond_status,
+            BondStatus::Refunded
+        );
+
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+        contract.on_creator_payout(0, account("usdc.testnet"), U128(BOND), true);
+        assert_eq!(
+            contract.markets.get(&0).unwrap().bond_status,
+            BondStatus::Held
+        );
+
+        set_context("market.testnet", vec![PromiseResult::Successful(vec![])]);
+        contract.on_creator_payout(0, account("usdc.testnet"), U128(BOND), true);
+        assert!(get_logs()[0].contains("\"event\":\"creation_bond_refunded\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Market is not settled")]
+    fn bond_not_refunded_before_settlement() {
+        let mut contract = test_contract().market().build();
+        contract.claim_creation_bond(0);
+    }
+
+    #[test]
+    fn curator_slashes_bond_to_treasury() {
+        let mut contract = test_contract().market().build();
+
+        set_context("curator.testnet", vec![]);
+        contract.slash_creation_bond(0, Some("Ambiguous question".to_string()));
+        assert!(get_logs()[0].contains("\"event\":\"creation_bond_slashed\""));
+        assert_eq!(
+            contract.markets.get(&0).unwrap().bond_status,
+            BondStatus::Slashed
+        );
+        assert_eq!(
+            contract.get_market(0).unwrap().bond_slash_reason.as_deref(),
+            Some("Ambiguous question")
+        );
+        assert_eq!(contract.get_protocol_revenue(None).balance, U128(BOND));
+        assert!(contract.check_invariants(0).ok);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot slash the bond of a settled market")]
+    fn settled_market_bond_cannot_be_slashed() {
+        let mut contract = test_contract().market().build();
+        settle(&mut contract, 0, Outcome::Yes);
+        set_context("curator.testnet", vec![]);
+        contract.slash_creation_bond(0, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only curators can do this")]
+    fn non_curator_cannot_slash() {
+        let mut contract = test_contract().market().build();
+        set_context("creator.testnet", vec![]);
+        contract.slash_creation_bond(0, None);
+    }
+
+    #[test]
+    fn creator_earns_and_claims_fee_share() {
+        let mut contract = test_contract().market().build();
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            100 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+
+        // 2% fee on 100 USDC = 2 USDC, 10% of which goes to the creator
+        let market = contract.markets.get(&0).unwrap();
+        assert_eq!(market.creator_fees, USDC_ONE / 5);
+        assert_eq!(market.accrued_fees, 2 * USDC_ONE - USDC_ONE / 5);
+        assert!(contract.check_invariants(0).ok);
+
+        set_context("creator.testnet", vec![]);
+        contract.claim_creator_fees(0);
+        assert_eq!(contract.markets.get(&0).unwrap().creator_fees, 0);
+        assert!(contract.check_invariants(0).ok);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the market creator can claim creator fees")]
+    fn only_creator_claims_fees() {
+        let mut contract = test_contract().market().build();
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            100 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+        set_context("buyer.testnet", vec![]);
+        contract.claim_creator_fees(0);
+    }
+}
@@ -0,0 +1,442 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseResult};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt};
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_REFUND_CALLBACK: Gas = Gas::from_tgas(10);
+const DEFAULT_PROPOSALS_LIMIT: u32 = 50;
+
+#[near]
+impl MarketContract {
+    // ── Proposal Review ────────────────────────────────────────────────
+
+    /// Create the proposed market from its held deposit. Curators only.
+    pub fn approve_proposal(&mut self, proposal_id: u64) -> MarketId {
+        let curator = self.assert_curator();
+        let proposal = self
+            .proposals
+            .remove(&proposal_id)
+            .expect("Proposal not found");
+        require!(
+            proposal.collateral_token == self.usdc_token,
+            "Collateral token changed since the proposal was made"
+        );
+
+        // The deposit is owed again once it backs the new market
+        self.debit_owed(&proposal.collateral_token, proposal.deposit);
+        let market_id =
+            self.internal_create_market(proposal.args, proposal.deposit, proposal.proposer);
+
+        Event::ProposalApproved {
+            proposal_id: U64(proposal_id),
+            market_id,
+            curator: &curator,
+        }
+        .emit();
+
+        market_id
+    }
+
+    /// Reject a proposal and refund its deposit to the proposer. Curators only.
+    pub fn reject_proposal(&mut self, proposal_id: u64, reason: Option<String>) -> Promise {
+        let curator = self.assert_curator();
+        let proposal = self
+            .proposals
+            .remove(&proposal_id)
+            .expect("Proposal not found");
+
+        Event::ProposalRejected {
+            proposal_id: U64(proposal_id),
+            curator: &curator,
+            reason: reason.as_deref(),
+        }
+        .emit();
+
+        self.internal_refund_proposal(proposal_id, proposal)
+    }
+
+    /// Withdraw a pending proposal and get the deposit back. Proposer only.
+    pub fn withdraw_proposal(&mut self, proposal_id: u64) -> Promise {
+        let proposal = self
+            .proposals
+            .remove(&proposal_id)
+            .expect("Proposal not found");
+        require!(
+            env::predecessor_account_id() == proposal.proposer,
+            "Only the proposer can withdraw a proposal"
+        );
+
+        Event::ProposalWithdrawn {
+            proposal_id: U64(proposal_id),
+            proposer: &proposal.proposer,
+        }
+        .emit();
+
+        self.internal_refund_proposal(proposal_id, proposal)
+    }
+
+    #[private]
+    pub fn on_proposal_refunded(
+        &mut self,
+        proposal_id: u64,
+        proposer: AccountId,
+        args: CreateMarketArgs,
+        deposit: U128,
+        collateral_token: AccountId,
+        proposed_at_ns: U64,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            "Expected one promise result"
+        );
+
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            // Refund failed — put the proposal back so it can be refunded again
+            self.credit_owed(&collateral_token, deposit.0);
+            self.proposals.insert(
+                proposal_id,
+                MarketProposal {
+                    proposer,
+                    args,
+                    deposit: deposit.0,
+                    collateral_token,
+                    proposed_at_ns: proposed_at_ns.0,
+                },
+            );
+            env::log_str(&format!(
+                "Refund of proposal {proposal_id} failed — proposal restored"
+            ));
+        }
+    }
+
+    // ── Creator Allowlist ──────────────────────────────────────────────
+
+    /// Add and remove allowlisted creators. Curators only.
+    pub fn update_creator_allowlist(&mut self, add: Vec<AccountId>, remove: Vec<AccountId>) {
+        self.assert_curator();
+        for account_id in &add {
+            self.creator_allowlist.insert(account_id.clone());
+        }
+        for account_id in &remove {
+            self.creator_allowlist.remove(account_id);
+        }
+
+        Event::CreatorAllowlistUpdated {
+            added: &add,
+            removed: &remove,
+        }
+        .emit();
+    }
+
+    // ── Views ──────────────────────────────────────────────────────────
+
+    pub fn get_proposal(&self, proposal_id: u64) -> Option<MarketProposalView> {
+        self.proposals
+            .get(&proposal_id)
+            .map(|proposal| proposal.to_view(proposal_id))
+    }
+
+    /// Pending proposals in storage order, paginated without loading the whole map.
+    pub fn get_proposals(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<MarketProposalView> {
+        self.proposals
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(DEFAULT_PROPOSALS_LIMIT) as usize)
+            .map(|(proposal_id, proposal)| proposal.to_view(*proposal_id))
+            .collect()
+    }
+
+    pub fn is_allowed_creator(&self, account_id: AccountId) -> bool {
+        self.creator_allowlist.contains(&account_id)
+    }
+
+    pub fn get_allowed_creators(
+        &self,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<AccountId> {
+        self.creator_allowlist
+            .iter()
+            .skip(from_index.unwrap_or(0) as usize)
+            .take(limit.unwrap_or(DEFAULT_PROPOSALS_LIMIT) as usize)
+            .cloned()
+            .collect()
+    }
+}
+
+impl MarketContract {
+    pub(crate) fn assert_curator(&self) -> AccountId {
+        let caller = env::predecessor_account_id();
+        require!(self.curators.contains(&caller), "Only curators can do this");
+        caller
+    }
+
+    /// Route a `CreateMarket` deposit according to the creation mode: create the
+    /// market directly, or hold the deposit as a proposal for curator review.
+    pub(crate) fn internal_submit_market(
+        &mut self,
+        args: CreateMarketArgs,
+        deposit: u128,
+        creator: AccountId,
+    ) {
+        let allowlisted = self.creator_allowlist.contains(&creator);
+        match self.creation_mode {
+            CreationMode::Open => {
+                self.internal_create_market(args, deposit, creator);
+            }
+            CreationMode::Allowlist => {
+                require!(allowlisted, "Creator is not allowlisted");
+                self.internal_create_market(args, deposit, creator);
+            }
+            CreationMode::Curated if allowlisted => {
+                self.internal_create_market(args, deposit, creator);
+            }
+            CreationMode::Curated => self.internal_propose_market(args, deposit, creator),
+        }
+    }
+
+    fn internal_propose_market(
+        &mut self,
+        args: CreateMarketArgs,
+        deposit: u128,
+        proposer: AccountId,
+    ) {
+        // Fail early; the args are checked again against the config at approval
+        self.validate_create_args(&args, deposit);
+
+        let proposal_id = self.next_proposal_id;
+        self.next_proposal_id += 1;
+        let collateral_token = self.usdc_token.clone();
+        self.credit_owed(&collateral_token, deposit);
+
+        Event::MarketProposed {
+            proposal_id: U64(proposal_id),
+            proposer: &proposer,
+            question: &args.question,
+            deposit: U128(deposit),
+        }
+        .emit();
+
+        self.proposals.insert(
+            proposal_id,
+            MarketProposal {
+                proposer,
+                args,
+                deposit,
+                collateral_token,
+                proposed_at_ns: env::block_timestamp(),
+            },
+        );
+    }
+
+    fn internal_refund_proposal(&mut self, proposal_id: u64, proposal: MarketProposal) -> Promise {
+        let MarketProposal {
+            proposer,
+            args,
+            deposit,
+            collateral_token,
+            proposed_at_ns,
+        } = proposal;
+        self.debit_owed(&collateral_token, deposit);
+
+        Promise::new(collateral_token.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "receiver_id": proposer,
+                    "amount": U128(deposit),
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(1),
+                GAS_FOR_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_proposal_refunded".to_string(),
+                    near_sdk::serde_json::json!({
+                        "proposal_id": proposal_id,
+                        "proposer": proposer,
+                        "args": args,
+                        "deposit": U128(deposit),
+                        "collateral_token": collateral_token,
+                        "proposed_at_ns": U64(proposed_at_ns),
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_REFUND_CALLBACK,
+                ),
+            )
+    }
+
+    /// Switch who may create markets. Applied through an admin action; pending
+    /// proposals stay reviewable in any mode.
+    pub(crate) fn set_creation_mode(&mut self, mode: CreationMode) {
+        self.creation_mode = mode;
+
+        Event::CreationModeUpdated { mode }.emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::test_utils::get_logs;
+
+    fn test_contract(mode: CreationMode) -> MarketContract {
+        ContractBuilder::new()
+            .curators(&["curator.testnet"])
+            .creation_mode(mode)
+            .build()
+    }
+
+    fn submit(contract: &mut MarketContract, creator: &str) {
+        submit_args(contract, creator, market_args());
+    }
+
+    fn submit_args(contract: &mut MarketContract, creator: &str, args: CreateMarketArgs) {
+        set_context("usdc.testnet", vec![]);
+        contract.ft_on_transfer(
+            account(creator),
+            U128(100 * USDC_ONE),
+            near_sdk::serde_json::to_string(&MarketFtMsg::CreateMarket(Box::new(args))).unwrap(),
+        );
+    }
+
+    fn owed(contract: &MarketContract) -> u128 {
+        contract.get_total_owed(account("usdc.testnet")).0
+    }
+
+    #[test]
+    fn curated_market_goes_live_after_approval() {
+        let mut contract = test_contract(CreationMode::Curated);
+        submit(&mut contract, "alice.testnet");
+        assert!(get_logs()[0].contains("\"event\":\"market_proposed\""));
+        assert!(contract.get_market(0).is_none());
+        assert_eq!(contract.get_proposals(None, None).len(), 1);
+        assert_eq!(owed(&contract), 100 * USDC_ONE);
+
+        set_context("curator.testnet", vec![]);
+        let market_id = contract.approve_proposal(0);
+        let market = contract.get_market(market_id).unwrap();
+        assert_eq!(market.creator, account("alice.testnet"));
+        assert_eq!(market.total_collateral, U128(100 * USDC_ONE));
+        assert!(contract.get_proposal(0).is_none());
+        assert_eq!(owed(&contract), 100 * USDC_ONE);
+    }
+
+    #[test]
+    fn proposal_view_shows_trading_restrictions() {
+        let mut contract = test_contract(CreationMode::Curated);
+        let limits = TradeLimits {
+            max_trade_collateral: Some(U128(10 * USDC_ONE)),
+            ..Default::default()
+        };
+        let priority = ResolutionPriority {
+            resolver: Some(account("reporter.testnet")),
+            window_ns: U64(1_000),
+        };
+        submit_args(
+            &mut contract,
+            "alice.testnet",
+            CreateMarketArgs {
+                access_policy: Some(AccessPolicy::Allowlist),
+                limits: Some(limits),
+                resolution_priority: Some(priority.clone()),
+                ..market_args()
+            },
+        );
+
+        let proposal = contract.get_proposal(0).unwrap();
+        assert_eq!(proposal.access_policy, Some(AccessPolicy::Allowlist));
+        assert_eq!(proposal.limits, Some(limits));
+        assert_eq!(proposal.resolution_priority, Some(priority));
+        assert!(contract.get_proposals(None, None)[0]
+            .access_policy
+            .is_some());
+    }
+
+    #[test]
+    fn rejected_proposal_is_refunded_and_restored_on_failure() {
+        let mut contract = test_contract(CreationMode::Curated);
+        submit(&mut contract, "alice.testnet");
+
+        set_context("curator.testnet", vec![]);
+        contract.reject_proposal(0, Some("Ambiguous".to_string()));
+        assert!(contract.get_proposal(0).is_none());
+        assert_eq!(owed(&contract), 0);
+
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+        contract.on_proposal_refunded(
+            0,
+            account("alice.testnet"),
+            market_args(),
+            U128(100 * USDC_ONE),
+            account("usdc.testnet"),
+            U64(0),
+        );
+        assert_eq!(
+            contract.get_proposal(0).unwrap().deposit,
+            U128(100 * USDC_ONE)
+        );
+        assert_eq!(owed(&contract), 100 * USDC_ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only curators can do this")]
+    fn only_curators_approve() {
+        let mut contract = test_contract(CreationMode::Curated);
+        submit(&mut contract, "alice.testnet");
+        set_context("alice.testnet", vec![]);
+        contract.approve_proposal(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Collateral token changed since the proposal was made")]
+    fn approval_requires_same_collateral() {
+        let mut contract = test_contract(CreationMode::Curated);
+        submit(&mut contract, "alice.testnet");
+        contract.usdc_token = account("usdc-v2.testnet");
+        set_context("curator.testnet", vec![]);
+        contract.approve_proposal(0);
+    }
+
+    #[test]
+    fn allowlisted_creator_skips_curation() {
+        let mut contract = test_contract(CreationMode::Curated);
+        set_context("curator.testnet", vec![]);
+        contract.update_creator_allowlist(vec![account("alice.testnet")], vec![]);
+        assert!(contract.is_allowed_creator(account("alice.testnet")));
+
+        submit(&mut contract, "alice.testnet");
+        assert!(contract.get_market(0).is_some());
+        assert!(contract.get_proposals(None, None).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Creator is not allowlisted")]
+    fn allowlist_mode_rejects_other_creators() {
+        let mut contract = test_contract(CreationMode::Allowlist);
+        submit(&mut contract, "bob.testnet");
+    }
+
+    #[test]
+    fn proposer_can_withdraw() {
+        let mut contract = test_contract(CreationMode::Curated);
+        submit(&mut contract, "alice.testnet");
+        set_context("alice.testnet", vec![]);
+        contract.withdraw_proposal(0);
+        assert!(get_logs()[0].contains("\"event\":\"proposal_withdrawn\""));
+        assert_eq!(owed(&contract), 0);
+    }
+}
@@ -4,10 +4,12 @@ use near_sdk::serde::Serialize;
 use near_sdk::serde_json::json;
 use near_sdk::AccountId;
 
-use market_types::{MarketId, Outcome};
+use market_types::{
+    AccessPolicy, AdminAction, CreationMode, DynamicFeeConfig, MarketId, MarketStatus, Outcome,
+};
 
 const EVENT_STANDARD: &str = "nest-markets";
-const EVENT_STANDARD_VERSION: &str = "1.0.0";
+const EVENT_STANDARD_VERSION: &str = "1.1.0";
 
 #[derive(Clone, Serialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -20,6 +22,34 @@ pub enum Event<'a> {
         resolution_time_ns: U64,
         creator: &'a AccountId,
         initial_liquidity: U128,
+        fee_bps: u16,
+        dynamic_fee: Option<DynamicFeeConfig>,
+        creation_bond: U128,
+        creator_fee_share_bps: u16,
+    },
+
+    MarketProposed {
+        proposal_id: U64,
+        proposer: &'a AccountId,
+        question: &'a str,
+        deposit: U128,
+    },
+
+    ProposalApproved {
+        proposal_id: U64,
+        market_id: MarketId,
+        curator: &'a AccountId,
+    },
+
+    ProposalRejected {
+        proposal_id: U64,
+        curator: &'a AccountId,
+        reason: Option<&'a str>,
+    },
+
+    ProposalWithdrawn {
+        proposal_id: U64,
+        proposer: &'a AccountId,
     },
 
     Trade {
@@ -31,6 +61,18 @@ pub enum Event<'a> {
         token_amount: U128,
         yes_price: U128,
         no_price: U128,
+        fee_bps: u16,
+        fee_amount: U128,
+        yes_reserve: U128,
+        no_reserve: U128,
+        total_collateral: U128,
+    },
+
+    FeesAccrued {
+        market_id: MarketId,
+        lp_fee: U128,
+        protocol_fee: U128,
+        creator_fee: U128,
     },
 
     LiquidityAdded {
@@ -38,6 +80,10 @@ pub enum Event<'a> {
         provider: &'a AccountId,
         amount: U128,
         lp_shares: U128,
+        yes_reserve: U128,
+        no_reserve: U128,
+        total_collateral: U128,
+        total_lp_shares: U128,
     },
 
     LiquidityRemoved {
@@ -45,6 +91,11 @@ pub enum Event<'a> {
         provider: &'a AccountId,
         amount: U128,
         lp_shares: U128,
+        fees_out: U128,
+        yes_reserve: U128,
+        no_reserve: U128,
+        total_collateral: U128,
+        total_lp_shares: U128,
     },
 
     ResolutionSubmitted {
@@ -54,11 +105,25 @@ pub enum Event<'a> {
         assertion_id: &'a str,
     },
 
+    ResolutionSubmissionFailed {
+        market_id: MarketId,
+        assertion_id: &'a str,
+        restored_status: MarketStatus,
+    },
+
     MarketDisputed {
         market_id: MarketId,
         assertion_id: &'a str,
     },
 
+    /// Oracle found the assertion false; market returns to `Closed` for re-resolution
+    AssertionRejected {
+        market_id: MarketId,
+        assertion_id: &'a str,
+        rejected_outcome: Option<Outcome>,
+        restored_status: MarketStatus,
+    },
+
     MarketSettled {
         market_id: MarketId,
         outcome: Outcome,
@@ -69,6 +134,167 @@ pub enum Event<'a> {
         user: &'a AccountId,
         collateral_out: U128,
     },
+
+    LosingTokensCleared {
+        market_id: MarketId,
+        user: &'a AccountId,
+        amount: U128,
+    },
+
+    /// Minting to `user` failed; the tokens can be claimed with `claim_unminted`.
+    MintDeferred {
+        market_id: MarketId,
+        user: &'a AccountId,
+        outcome: Outcome,
+        amount: U128,
+    },
+
+    AccessPolicyUpdated {
+        market_id: MarketId,
+        policy: &'a AccessPolicy,
+    },
+
+    MarketAllowlistUpdated {
+        market_id: MarketId,
+        added: &'a [AccountId],
+        removed: &'a [AccountId],
+    },
+
+    CreationBondRefunded {
+        market_id: MarketId,
+        creator: &'a AccountId,
+        amount: U128,
+    },
+
+    CreationBondSlashed {
+        market_id: MarketId,
+        curator: &'a AccountId,
+        amount: U128,
+        reason: Option<&'a str>,
+    },
+
+    CreatorFeesClaimed {
+        market_id: MarketId,
+        creator: &'a AccountId,
+        amount: U128,
+    },
+
+    OwnerChanged {
+        old_owner: &'a AccountId,
+        new_owner: &'a AccountId,
+    },
+
+    EmergencyTokenWithdrawal {
+        token: &'a AccountId,
+        receiver_id: &'a AccountId,
+        amount: U128,
+        forced: bool,
+    },
+
+    EmergencyNearWithdrawal {
+        receiver_id: &'a AccountId,
+        amount: U128,
+    },
+
+    AdminActionProposed {
+        action_id: U64,
+        action: &'a AdminAction,
+        proposer: &'a AccountId,
+        executable_at_ns: U64,
+    },
+
+    AdminActionApproved {
+        action_id: U64,
+        approver: &'a AccountId,
+        approvals: u32,
+    },
+
+    AdminActionExecuted {
+        action_id: U64,
+        action: &'a AdminAction,
+    },
+
+    AdminActionCancelled {
+        action_id: U64,
+        cancelled_by: &'a AccountId,
+    },
+
+    GuardianChanged {
+        old_guardian: &'a AccountId,
+        new_guardian: &'a AccountId,
+    },
+
+    TimelockDelayUpdated {
+        delay_ns: U64,
+    },
+
+    ApproversUpdated {
+        approvers: &'a [AccountId],
+        threshold: u32,
+    },
+
+    ActionTtlUpdated {
+        ttl_ns: U64,
+    },
+
+    OracleUpdated {
+        old_oracle: &'a AccountId,
+        new_oracle: &'a AccountId,
+    },
+
+    OutcomeTokenUpdated {
+        old_outcome_token: &'a AccountId,
+        new_outcome_token: &'a AccountId,
+    },
+
+    CollateralTokenUpdated {
+        old_collateral_token: &'a AccountId,
+        new_collateral_token: &'a AccountId,
+    },
+
+    FeeTiersUpdated {
+        default_fee_bps: u16,
+        fee_tiers: &'a [u16],
+    },
+
+    ProtocolFeeShareUpdated {
+        protocol_fee_share_bps: u16,
+    },
+
+    CreationBondUpdated {
+        amount: U128,
+    },
+
+    EarlyResolutionBondUpdated {
+        amount: U128,
+    },
+
+    CreatorFeeShareUpdated {
+        creator_fee_share_bps: u16,
+    },
+
+    CuratorsUpdated {
+        curators: &'a [AccountId],
+    },
+
+    CreationModeUpdated {
+        mode: CreationMode,
+    },
+
+    CreatorAllowlistUpdated {
+        added: &'a [AccountId],
+        removed: &'a [AccountId],
+    },
+
+    TreasurerUpdated {
+        treasurer: &'a AccountId,
+    },
+
+    ProtocolFeesWithdrawn {
+        token: &'a AccountId,
+        receiver_id: &'a AccountId,
+        amount: U128,
+    },
 }
 
 impl Event<'_> {
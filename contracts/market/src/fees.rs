@@ -0,0 +1,375 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseResult};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt};
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_WITHDRAW_CALLBACK: Gas = Gas::from_tgas(10);
+
+#[near]
+impl MarketContract {
+    // ── Treasury Withdrawal ────────────────────────────────────────────
+
+    /// Withdraw accumulated protocol fees to the treasurer. Only the treasurer
+    /// may call this; changing the treasurer goes through an admin action.
+    /// Defaults to the current collateral token.
+    pub fn withdraw_protocol_fees(&mut self, amount: U128, token: Option<AccountId>) -> Promise {
+        require!(
+            env::predecessor_account_id() == self.treasurer,
+            "Only the treasurer can withdraw protocol fees"
+        );
+        let amount = amount.0;
+        require!(amount > 0, "Amount must be positive");
+        let token = token.unwrap_or_else(|| self.usdc_token.clone());
+        let balance = self.protocol_fee_balance(&token);
+        require!(amount <= balance, "Insufficient protocol fee balance");
+
+        let receiver_id = self.treasurer.clone();
+        // Debit up front so a concurrent call cannot withdraw the same fees twice
+        self.protocol_fee_balances
+            .insert(token.clone(), balance - amount);
+        self.debit_owed(&token, amount);
+
+        Promise::new(token.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "receiver_id": receiver_id,
+                    "amount": U128(amount),
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(1),
+                GAS_FOR_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_protocol_fees_withdrawn".to_string(),
+                    near_sdk::serde_json::json!({
+                        "receiver_id": receiver_id,
+                        "amount": U128(amount),
+                        "token": token,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_WITHDRAW_CALLBACK,
+                ),
+            )
+    }
+
+    #[private]
+    pub fn on_protocol_fees_withdrawn(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        token: AccountId,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            "Expected one promise result"
+        );
+
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => {
+                Event::ProtocolFeesWithdrawn {
+                    token: &token,
+                    receiver_id: &receiver_id,
+                    amount,
+                }
+                .emit();
+            }
+            _ => {
+                // Transfer failed — credit the fees back to the treasury balance
+                let balance = self.protocol_fee_balance(&token) + amount.0;
+                self.protocol_fee_balances.insert(token.clone(), balance);
+                self.credit_owed(&token, amount.0);
+                env::log_str(&format!(
+                    "Protocol fee withdrawal of {} to {receiver_id} failed — balance restored",
+                    amount.0
+                ));
+            }
+        }
+    }
+
+    // ── Fee Tier Config ────────────────────────────────────────────────
+
+    /// Set the fee applied when a creator does not pick a tier. Applied through an
+    /// admin action. Must be one of the approved tiers; existing markets keep their fee.
+    pub(crate) fn set_default_fee_bps(&mut self, default_fee_bps: u16) {
+        require!(
+            self.fee_tiers.contains(&default_fee_bps),
+            "Default fee must be an approved tier"
+        );
+        self.default_fee_bps = default_fee_bps;
+        self.emit_fee_tiers_updated();
+    }
+
+    /// Replace the list of fee tiers creators may choose from.
+    /// Applied through an admin action. The list must keep the current default fee.
+    pub(crate) fn set_fee_tiers(&mut self, fee_tiers: Vec<u16>) {
+        let mut fee_tiers = fee_tiers;
+        fee_tiers.sort_unstable();
+        fee_tiers.dedup();
+        require!(
+            fee_tiers.iter().all(|bps| *bps <= MAX_FEE_BPS),
+            format!("Fee tiers cannot exceed {} bps", MAX_FEE_BPS)
+        );
+        require!(
+            fee_tiers.contains(&self.default_fee_bps),
+            "Fee tiers must include the default fee"
+        );
+        self.fee_tiers = fee_tiers;
+        self.emit_fee_tiers_updated();
+    }
+
+    // ── Protocol Fee Config ────────────────────────────────────────────
+
+    /// Set the share of each trading fee routed to the treasury.
+    /// Applied through an admin action; affects fees collected afterwards.
+    pub(crate) fn set_protocol_fee_share_bps(&mut self, protocol_fee_share_bps: u16) {
+        require!(
+            protocol_fee_share_bps <= BPS_DENOMINATOR,
+            "Protocol fee share cannot exceed 100%"
+        );
+        self.protocol_fee_share_bps = protocol_fee_share_bps;
+
+        Event::ProtocolFeeShareUpdated {
+            protocol_fee_share_bps,
+        }
+        .emit();
+    }
+
+    /// Set the account allowed to withdraw protocol fees. Applied through an admin action.
+    pub(crate) fn set_treasurer(&mut self, treasurer: AccountId) {
+        self.treasurer = treasurer;
+
+        Event::TreasurerUpdated {
+            treasurer: &self.treasurer,
+        }
+        .emit();
+    }
+
+    // ── Helpers ────────────────────────────────────────────────────────
+
+    pub(crate) fn protocol_fee_balance(&self, token: &AccountId) -> u128 {
+        self.protocol_fee_balances.get(token).copied().unwrap_or(0)
+    }
+
+    pub(crate) fn protocol_fees_accrued(&self, token: &AccountId) -> u128 {
+        self.protocol_fees_accrued.get(token).copied().unwrap_or(0)
+    }
+
+    fn emit_fee_tiers_updated(&self) {
+        Event::FeeTiersUpdated {
+            default_fee_bps: self.default_fee_bps,
+            fee_tiers: &self.fee_tiers,
+        }
+        .emit();
+    }
+
+    /// Split a trading fee between the treasury, the market creator and the market's LPs.
+    pub(crate) fn accrue_fee(&mut self, market: &mut Market, fee: u128) {
+        let protocol_fee = fee * self.protocol_fee_share_bps as u128 / BPS_DENOMINATOR as u128;
+        let creator_fee = (fee * market.creator_fee_share_bps as u128 / BPS_DENOMINATOR as u128)
+            .min(fee - protocol_fee);
+        let lp_fee = fee - protocol_fee - creator_fee;
+
+        market.accrued_fees += lp_fee;
+        market.protocol_fees += protocol_fee;
+        market.creator_fees += creator_fee;
+        let token = &market.collateral_token;
+        let balance = self.protocol_fee_balance(token) + protocol_fee;
+        let accrued = self.protocol_fees_accrued(token) + protocol_fee;
+        self.protocol_fee_balances.insert(token.clone(), balance);
+        self.protocol_fees_accrued.insert(token.clone(), accrued);
+
+        if fee > 0 {
+            Event::FeesAccrued {
+                market_id: market.id,
+                lp_fee: U128(lp_fee),
+                protocol_fee: U128(protocol_fee),
+                creator_fee: U128(creator_fee),
+            }
+            .emit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::json_types::U64;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn test_contract() -> ContractBuilder {
+        ContractBuilder::new()
+            .treasurer("treasury.testnet")
+            .protocol_fee_share_bps(2_500)
+    }
+
+    #[test]
+    fn buy_fee_is_split_between_lps_and_treasury() {
+        let mut contract = test_contract().market().build();
+        set_context("usdc.testnet", vec![]);
+
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            100 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+
+        // 2% fee on 100 USDC = 2 USDC, 25% of which goes to the treasury
+        let market = contract.markets.get(&0).unwrap();
+        assert_eq!(market.protocol_fees, USDC_ONE / 2);
+        assert_eq!(market.accrued_fees, 3 * USDC_ONE / 2);
+        assert_eq!(
+            contract.protocol_fee_balance(&account("usdc.testnet")),
+            USDC_ONE / 2
+        );
+        assert_eq!(
+            contract.get_protocol_revenue(None).total_accrued,
+            U128(USDC_ONE / 2)
+        );
+    }
+
+    #[test]
+    fn withdraw_debits_balance_and_failed_transfer_restores_it() {
+        let mut contract = test_contract().build();
+        let usdc = account("usdc.testnet");
+        contract
+            .protocol_fee_balances
+            .insert(usdc.clone(), 10 * USDC_ONE);
+        contract
+            .protocol_fees_accrued
+            .insert(usdc.clone(), 10 * USDC_ONE);
+
+        set_context("treasury.testnet", vec![]);
+        contract.withdraw_protocol_fees(U128(4 * USDC_ONE), None);
+        assert_eq!(contract.protocol_fee_balance(&usdc), 6 * USDC_ONE);
+
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+        contract.on_protocol_fees_withdrawn(
+            account("treasury.testnet"),
+            U128(4 * USDC_ONE),
+            usdc.clone(),
+        );
+        assert_eq!(contract.protocol_fee_balance(&usdc), 10 * USDC_ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the treasurer can withdraw protocol fees")]
+    fn owner_cannot_withdraw_protocol_fees() {
+        let mut contract = test_contract().build();
+        contract
+            .protocol_fee_balances
+            .insert(account("usdc.testnet"), 10 * USDC_ONE);
+        set_context("owner.testnet", vec![]);
+        contract.withdraw_protocol_fees(U128(USDC_ONE), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient protocol fee balance")]
+    fn withdraw_rejects_more_than_balance() {
+        let mut contract = test_contract().build();
+        contract
+            .protocol_fee_balances
+            .insert(account("usdc.testnet"), USDC_ONE);
+        set_context("treasury.testnet", vec![]);
+        contract.withdraw_protocol_fees(U128(2 * USDC_ONE), None);
+    }
+
+    #[test]
+    fn creator_can_pick_an_approved_fee_tier() {
+        let mut contract = test_contract().build();
+        set_context("usdc.testnet", vec![]);
+
+        create_market(
+            &mut contract,
+            CreateMarketArgs {
+                fee_bps: Some(500),
+                ..market_args()
+            },
+            10 * USDC_ONE,
+        );
+
+        assert_eq!(contract.markets.get(&0).unwrap().fee_bps, 500);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fee tier is not approved")]
+    fn create_market_rejects_unapproved_fee_tier() {
+        let mut contract = test_contract().build();
+        set_context("usdc.testnet", vec![]);
+
+        create_market(
+            &mut contract,
+            CreateMarketArgs {
+                fee_bps: Some(300),
+                ..market_args()
+            },
+            10 * USDC_ONE,
+        );
+    }
+
+    #[test]
+    fn owner_updates_default_fee_and_tiers() {
+        let mut contract = test_contract().build();
+        set_context("owner.testnet", vec![]);
+
+        contract.set_fee_tiers(vec![500, 30, 200, 30]);
+        contract.set_default_fee_bps(30);
+
+        let config = contract.get_config();
+        assert_eq!(config.fee_tiers, vec![30, 200, 500]);
+        assert_eq!(config.default_fee_bps, 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "Fee tiers must include the default fee")]
+    fn fee_tiers_must_keep_default() {
+        let mut contract = test_contract().build();
+        set_context("owner.testnet", vec![]);
+        contract.set_fee_tiers(vec![100, 500]);
+    }
+
+    #[test]
+    fn dynamic_fee_ramps_toward_resolution_and_applies_to_estimates() {
+        let contract = test_contract()
+            .market_with(CreateMarketArgs {
+                resolution_time_ns: U64(1_000_000),
+                dynamic_fee: Some(DynamicFeeConfig {
+                    ramp_window_ns: U64(1_000),
+                    max_fee_bps: 1_000,
+                }),
+                ..market_args()
+            })
+            .build();
+        let market = contract.markets.get(&0).unwrap().clone();
+
+        assert_eq!(market.fee_bps_at(0), DEFAULT_FEE_BPS);
+        assert_eq!(market.fee_bps_at(999_000), DEFAULT_FEE_BPS);
+        assert_eq!(market.fee_bps_at(999_500), 600);
+        assert_eq!(market.fee_bps_at(1_000_000), 1_000);
+
+        testing_env!(VMContextBuilder::new().block_timestamp(0).build());
+        let early = contract.estimate_buy(0, Outcome::Yes, U128(10 * USDC_ONE));
+        testing_env!(VMContextBuilder::new().block_timestamp(999_500).build());
+        let late = contract.estimate_buy(0, Outcome::Yes, U128(10 * USDC_ONE));
+        assert_eq!(contract.get_current_fee_bps(0), 600);
+        assert!(
+            late.0 < early.0,
+            "late estimate {} should be below {}",
+            late.0,
+            early.0
+        );
+    }
+}
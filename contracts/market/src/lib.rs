@@ -1,19 +1,30 @@
+mod access;
+mod admin;
 mod amm;
+mod creator;
+mod curation;
 mod events;
+mod fees;
+mod migration;
+mod mints;
 mod redemption;
 mod resolution;
+mod solvency;
+mod twap;
 mod views;
 
-use near_sdk::json_types::U128;
-use near_sdk::store::LookupMap;
-use near_sdk::{env, near, require, AccountId, Gas, NearToken, PanicOnDefault, Promise};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::store::{IterableMap, IterableSet, LookupMap, LookupSet};
+use near_sdk::{
+    env, near, require, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue,
+};
 
 use market_types::*;
 
 use events::Event;
 
 pub const GAS_FOR_MINT: Gas = Gas::from_tgas(10);
-pub const GAS_FOR_MINT_CALLBACK: Gas = Gas::from_tgas(5);
+pub const GAS_FOR_MINT_CALLBACK: Gas = Gas::from_tgas(10);
 
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
@@ -21,13 +32,13 @@ pub struct MarketContract {
     /// Contract owner
     owner: AccountId,
 
-    /// USDC token contract
+    /// USDC token contract used as collateral by new markets
     usdc_token: AccountId,
 
-    /// Outcome token contract
+    /// Outcome token contract used by new markets
     outcome_token: AccountId,
 
-    /// Nest Optimistic Oracle contract
+    /// Nest Optimistic Oracle contract used by new markets
     oracle: AccountId,
 
     /// All markets
@@ -41,113 +52,211 @@ pub struct MarketContract {
 
     /// Mapping from oracle assertion_id to market_id
     assertion_to_market: LookupMap<Bytes32, MarketId>,
+
+    /// Only account allowed to withdraw protocol fees, always to itself
+    treasurer: AccountId,
+
+    /// Share of each trading fee routed to the treasury, in basis points of the fee
+    protocol_fee_share_bps: u16,
+
+    /// Protocol fees held by the contract and not yet withdrawn, per collateral token
+    protocol_fee_balances: LookupMap<AccountId, u128>,
+
+    /// Lifetime protocol fees across all markets, per collateral token
+    protocol_fees_accrued: LookupMap<AccountId, u128>,
+
+    /// Fee applied when a creator does not pick a tier
+    default_fee_bps: u16,
+
+    /// Owner-approved fee tiers creators may choose from
+    fee_tiers: Vec<u16>,
+
+    /// Per-market ring buffer of cumulative price observations for TWAP
+    price_observations: LookupMap<MarketId, PriceObservations>,
+
+    /// Amount of each token the contract owes to LPs, traders and the treasury
+    total_owed: LookupMap<AccountId, u128>,
+
+    /// Account allowed to cancel queued admin actions
+    guardian: AccountId,
+
+    /// Delay between proposing and executing an admin action
+    timelock_delay_ns: u64,
+
+    /// Accounts that propose and approve admin actions
+    approvers: Vec<AccountId>,
+
+    /// Approvals an admin action needs before it can be executed
+    approval_threshold: u32,
+
+    /// How long an admin action stays executable once its timelock has passed
+    action_ttl_ns: u64,
+
+    /// Queued admin actions by id
+    pending_actions: IterableMap<u64, PendingAction>,
+
+    /// Id assigned to the next proposed admin action
+    next_action_id: u64,
+
+    /// Refundable bond new market creators post on top of the initial liquidity
+    creation_bond: u128,
+
+    /// Share of each trading fee paid to the creator of new markets, in basis points of the fee
+    creator_fee_share_bps: u16,
+
+    /// Accounts allowed to slash creation bonds, review proposals and manage the allowlist
+    curators: Vec<AccountId>,
+
+    /// Who may create markets
+    creation_mode: CreationMode,
+
+    /// Creators allowed to create markets without curator approval
+    creator_allowlist: IterableSet<AccountId>,
+
+    /// Markets waiting for curator approval by proposal id
+    proposals: IterableMap<u64, MarketProposal>,
+
+    /// Id assigned to the next market proposal
+    next_proposal_id: u64,
+
+    /// Per-market trader allowlists: compound key (market_id + account)
+    market_allowlists: LookupSet<Vec<u8>>,
+
+    /// Outcome tokens being minted to each account, tracked for markets with a
+    /// holding cap: compound key (market_id + account + outcome) -> amount
+    pending_mints: LookupMap<Vec<u8>, u128>,
+
+    /// Minimum bond for an assertion made before a market's resolution time
+    early_resolution_bond: u128,
+
+    /// Outcome tokens owed to accounts whose mint failed, claimable with
+    /// `claim_unminted`: compound key (market_id + account + outcome) -> amount
+    unminted: LookupMap<Vec<u8>, u128>,
 }
 
 #[near]
 impl MarketContract {
+    /// `first_market_id` (default 0) lets a second or redeployed market contract
+    /// sharing an outcome token start at its own minter scope.
     #[init]
     pub fn new(
         owner: AccountId,
         usdc_token: AccountId,
         outcome_token: AccountId,
         oracle: AccountId,
+        governance: Option<GovernanceConfig>,
+        first_market_id: Option<MarketId>,
     ) -> Self {
+        let governance = governance.unwrap_or_else(|| GovernanceConfig {
+            approvers: vec![owner.clone()],
+            threshold: 1,
+            timelock_delay_ns: U64(DEFAULT_TIMELOCK_DELAY_NS),
+            action_ttl_ns: U64(DEFAULT_ACTION_TTL_NS),
+        });
+        Self::validate_approvers(&governance.approvers, governance.threshold);
+        require!(
+            governance.timelock_delay_ns.0 <= MAX_TIMELOCK_DELAY_NS,
+            "Timelock delay exceeds maximum"
+        );
+        require!(
+            governance.action_ttl_ns.0 > 0,
+            "Action TTL must be positive"
+        );
+
         Self {
+            treasurer: owner.clone(),
+            guardian: owner.clone(),
             owner,
             usdc_token,
             outcome_token,
             oracle,
             markets: LookupMap::new(b"m"),
-            market_count: 0,
+            market_count: first_market_id.unwrap_or(0),
             lp_positions: LookupMap::new(b"l"),
             assertion_to_market: LookupMap::new(b"a"),
+            protocol_fee_share_bps: DEFAULT_PROTOCOL_FEE_SHARE_BPS,
+            protocol_fee_balances: LookupMap::new(b"f"),
+            protocol_fees_accrued: LookupMap::new(b"t"),
+            default_fee_bps: DEFAULT_FEE_BPS,
+            fee_tiers: DEFAULT_FEE_TIERS.to_vec(),
+            price_observations: LookupMap::new(b"o"),
+            total_owed: LookupMap::new(b"w"),
+            timelock_delay_ns: governance.timelock_delay_ns.0,
+            approvers: governance.approvers,
+            approval_threshold: governance.threshold,
+            action_ttl_ns: governance.action_ttl_ns.0,
+            pending_actions: IterableMap::new(b"q"),
+            next_action_id: 0,
+            creation_bond: DEFAULT_CREATION_BOND,
+            creator_fee_share_bps: DEFAULT_CREATOR_FEE_SHARE_BPS,
+            curators: Vec::new(),
+            creation_mode: CreationMode::Open,
+            creator_allowlist: IterableSet::new(b"c"),
+            proposals: IterableMap::new(b"p"),
+            next_proposal_id: 0,
+            market_allowlists: LookupSet::new(b"x"),
+            pending_mints: LookupMap::new(b"n"),
+            early_resolution_bond: DEFAULT_EARLY_RESOLUTION_BOND,
+            unminted: LookupMap::new(b"u"),
         }
     }
 
-    // ── Admin / Emergency Controls ─────────────────────────────────────
-
-    /// Transfer contract ownership.
-    pub fn set_owner(&mut self, new_owner: AccountId) {
-        self.assert_owner();
-        self.owner = new_owner;
-    }
+    // ── ft_on_transfer Router ──────────────────────────────────────────
 
-    /// Emergency token withdrawal for stuck funds recovery.
-    /// Owner-only: this can move core funds and should only be used operationally.
-    pub fn emergency_withdraw_token(
+    pub fn ft_on_transfer(
         &mut self,
-        token: AccountId,
-        receiver_id: AccountId,
+        sender_id: AccountId,
         amount: U128,
-    ) -> Promise {
-        self.assert_owner();
-        require!(amount.0 > 0, "Amount must be positive");
-
-        Promise::new(token).function_call(
-            "ft_transfer".to_string(),
-            near_sdk::serde_json::json!({
-                "receiver_id": receiver_id,
-                "amount": amount,
-            })
-            .to_string()
-            .into_bytes(),
-            NearToken::from_yoctonear(1),
-            Gas::from_tgas(10),
-        )
-    }
-
-    /// Emergency native NEAR withdrawal for stuck balance recovery.
-    /// Owner-only.
-    pub fn emergency_withdraw_near(&mut self, receiver_id: AccountId, amount: U128) -> Promise {
-        self.assert_owner();
-        require!(amount.0 > 0, "Amount must be positive");
-        require!(
-            env::account_balance() >= NearToken::from_yoctonear(amount.0),
-            "Insufficient balance"
-        );
-
-        Promise::new(receiver_id).transfer(NearToken::from_yoctonear(amount.0))
-    }
-
-    // ── ft_on_transfer Router ──────────────────────────────────────────
-
-    pub fn ft_on_transfer(&mut self, sender_id: AccountId, amount: U128, msg: String) -> U128 {
+        msg: String,
+    ) -> PromiseOrValue<U128> {
         let token = env::predecessor_account_id();
-        require!(token == self.usdc_token, "Only USDC is accepted");
-
         let parsed: MarketFtMsg =
             near_sdk::serde_json::from_str(&msg).expect("Invalid ft_on_transfer message");
 
+        // New markets use the current collateral, existing ones the token they were created with
+        let collateral_token = match &parsed {
+            MarketFtMsg::CreateMarket(_) => &self.usdc_token,
+            MarketFtMsg::Buy { market_id, .. }
+            | MarketFtMsg::AddLiquidity { market_id }
+            | MarketFtMsg::SubmitResolution { market_id, .. } => {
+                &self
+                    .markets
+                    .get(market_id)
+                    .expect("Market not found")
+                    .collateral_token
+            }
+        };
+        require!(
+            token == *collateral_token,
+            "Token is not the market's collateral"
+        );
+
         match parsed {
-            MarketFtMsg::CreateMarket {
-                question,
-                description,
-                resolution_time_ns,
-            } => {
-                self.internal_create_market(
-                    question,
-                    description,
-                    resolution_time_ns.0,
-                    amount.0,
-                    sender_id,
-                );
-                U128(0) // all tokens used
+            MarketFtMsg::CreateMarket(args) => {
+                self.internal_submit_market(*args, amount.0, sender_id);
+                PromiseOrValue::Value(U128(0)) // all tokens used or held with the proposal
             }
+            // Token-gated markets check the sender's balance first; a failed
+            // check panics in the callback and the transfer is refunded
             MarketFtMsg::Buy {
                 market_id,
                 outcome,
                 min_tokens_out,
-            } => {
-                self.internal_buy(market_id, outcome, amount.0, min_tokens_out.0, sender_id);
-                U128(0)
-            }
+            } => self.internal_gated(
+                market_id,
+                sender_id,
+                GatedAction::Buy {
+                    outcome,
+                    collateral: amount,
+                    min_tokens_out,
+                },
+            ),
             MarketFtMsg::AddLiquidity { market_id } => {
-                self.internal_add_liquidity(market_id, amount.0, sender_id);
-                U128(0)
+                self.internal_gated(market_id, sender_id, GatedAction::AddLiquidity { amount })
             }
             MarketFtMsg::SubmitResolution { market_id, outcome } => {
                 self.internal_submit_resolution(market_id, outcome, amount.0, sender_id);
-                U128(0)
+                PromiseOrValue::Value(U128(0))
             }
         }
     }
@@ -156,24 +265,23 @@ impl MarketContract {
 
     fn internal_create_market(
         &mut self,
-        question: String,
-        description: String,
-        resolution_time_ns: u64,
-        initial_liquidity: u128,
+        args: CreateMarketArgs,
+        deposit: u128,
         creator: AccountId,
-    ) {
-        require!(
-            initial_liquidity >= MIN_INITIAL_LIQUIDITY,
-            format!(
-                "Minimum initial liquidity is {} USDC",
-                MIN_INITIAL_LIQUIDITY / USDC_ONE
-            )
-        );
-        require!(
-            resolution_time_ns > env::block_timestamp(),
-            "Resolution time must be in the future"
-        );
-        require!(!question.is_empty(), "Question cannot be empty");
+    ) -> MarketId {
+        let (initial_liquidity, fee_bps) = self.validate_create_args(&args, deposit);
+        let creation_bond = self.creation_bond;
+        let CreateMarketArgs {
+            question,
+            description,
+            resolution_time_ns,
+            dynamic_fee,
+            access_policy,
+            limits,
+            resolution_priority,
+            ..
+        } = args;
+        let resolution_time_ns = resolution_time_ns.0;
 
         let market_id = self.market_count;
         self.market_count += 1;
@@ -188,38 +296,66 @@ impl MarketContract {
             resolution_time_ns,
             status: MarketStatus::Open,
             outcome: None,
+            collateral_token: self.usdc_token.clone(),
+            outcome_token: self.outcome_token.clone(),
+            oracle: self.oracle.clone(),
             yes_reserve: half,
             no_reserve: half,
             total_lp_shares: initial_liquidity,
             total_collateral: initial_liquidity,
-            fee_bps: DEFAULT_FEE_BPS,
+            yes_supply: half,
+            no_supply: half,
+            fee_bps,
+            dynamic_fee,
             accrued_fees: 0,
+            protocol_fees: 0,
+            creation_bond,
+            bond_status: if creation_bond > 0 {
+                BondStatus::Held
+            } else {
+                BondStatus::NotRequired
+            },
+            bond_slash_reason: None,
+            creator_fee_share_bps: self.creator_fee_share_bps,
+            creator_fees: 0,
+            access_policy: access_policy.unwrap_or(AccessPolicy::Open),
+            limits: limits.unwrap_or_default(),
+            resolution_priority,
             assertion_id: None,
             asserted_outcome: None,
             resolver: None,
             disputer: None,
             assertion_submitted_at_ns: None,
             assertion_expires_at_ns: None,
+            early_assertion: false,
+            assertion_claim: None,
         };
 
         // Record LP position for creator
         let lp_key = Self::lp_key(market_id, &creator);
         self.lp_positions.insert(lp_key, initial_liquidity);
+        self.credit_owed(&market.collateral_token, deposit);
 
         Event::MarketCreated {
             market_id,
             question: &question,
-            resolution_time_ns: near_sdk::json_types::U64(resolution_time_ns),
+            resolution_time_ns: U64(resolution_time_ns),
             creator: &creator,
             initial_liquidity: U128(initial_liquidity),
+            fee_bps,
+            dynamic_fee,
+            creation_bond: U128(creation_bond),
+            creator_fee_share_bps: self.creator_fee_share_bps,
         }
         .emit();
 
+        self.record_price_observation(&market);
+        let outcome_token = market.outcome_token.clone();
         self.markets.insert(market_id, market);
 
         // Mint YES+NO tokens to contract to back reserves
         let contract_id = env::current_account_id();
-        Promise::new(self.outcome_token.clone())
+        Promise::new(outcome_token.clone())
             .function_call(
                 "mint".to_string(),
                 near_sdk::serde_json::json!({
@@ -234,7 +370,7 @@ impl MarketContract {
                 GAS_FOR_MINT,
             )
             .and(
-                Promise::new(self.outcome_token.clone()).function_call(
+                Promise::new(outcome_token).function_call(
                     "mint".to_string(),
                     near_sdk::serde_json::json!({
                         "market_id": market_id,
@@ -248,12 +384,259 @@ impl MarketContract {
                     GAS_FOR_MINT,
                 ),
             );
+
+        market_id
     }
 
-    fn assert_owner(&self) {
+    /// Check creation args against the current config. Returns the initial
+    /// liquidity left after the creation bond and the fee tier to use.
+    fn validate_create_args(&self, args: &CreateMarketArgs, deposit: u128) -> (u128, u16) {
+        // The creation bond comes out of the deposit, the rest seeds the pool
+        let creation_bond = self.creation_bond;
+        let initial_liquidity = deposit.checked_sub(creation_bond).unwrap_or_else(|| {
+            env::panic_str(&format!(
+                "Deposit must cover the creation bond of {creation_bond}"
+            ))
+        });
+        require!(
+            initial_liquidity >= MIN_INITIAL_LIQUIDITY,
+            format!(
+                "Minimum initial liquidity is {} USDC",
+                MIN_INITIAL_LIQUIDITY / USDC_ONE
+            )
+        );
         require!(
-            env::predecessor_account_id() == self.owner,
-            "Only owner can call this method"
+            args.resolution_time_ns.0 > env::block_timestamp(),
+            "Resolution time must be in the future"
+        );
+        require!(!args.question.is_empty(), "Question cannot be empty");
+        let fee_bps = args.fee_bps.unwrap_or(self.default_fee_bps);
+        require!(
+            self.fee_tiers.contains(&fee_bps),
+            "Fee tier is not approved"
+        );
+        if let Some(schedule) = args.dynamic_fee {
+            require!(
+                schedule.ramp_window_ns.0 > 0,
+                "Dynamic fee ramp window must be positive"
+            );
+            require!(
+                schedule.max_fee_bps >= fee_bps && schedule.max_fee_bps <= MAX_FEE_BPS,
+                format!(
+                    "Dynamic max fee must be between the base fee and {} bps",
+                    MAX_FEE_BPS
+                )
+            );
+        }
+        if let Some(policy) = &args.access_policy {
+            Self::validate_access_policy(policy);
+        }
+        if let Some(limits) = &args.limits {
+            require!(
+                limits.max_trade_collateral.is_none_or(|max| max.0 > 0)
+                    && limits.max_holding.is_none_or(|max| max.0 > 0),
+                "Trade limits must be positive"
+            );
+            require!(
+                limits
+                    .max_price_impact_bps
+                    .is_none_or(|max| max > 0 && max <= BPS_DENOMINATOR),
+                "Max price impact must be between 1 and 10000 bps"
+            );
+        }
+        if let Some(priority) = &args.resolution_priority {
+            require!(
+                priority.window_ns.0 > 0
+                    && priority.window_ns.0 <= MAX_RESOLUTION_PRIORITY_WINDOW_NS,
+                "Resolution priority window must be positive and at most 7 days"
+            );
+        }
+        (initial_liquidity, fee_bps)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::{testing_env, PromiseResult};
+
+    use super::*;
+
+    pub(crate) fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    /// Call as `predecessor` at timestamp 0, with the given callback results.
+    pub(crate) fn set_context(predecessor: &str, promise_results: Vec<PromiseResult>) {
+        let mut builder = VMContextBuilder::new();
+        builder
+            .predecessor_account_id(account(predecessor))
+            .current_account_id(account("market.testnet"));
+
+        testing_env!(
+            builder.build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            promise_results
         );
     }
+
+    /// Call as `predecessor` at `timestamp_ns`.
+    pub(crate) fn set_context_at(predecessor: &str, timestamp_ns: u64) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account(predecessor))
+            .current_account_id(account("market.testnet"))
+            .block_timestamp(timestamp_ns)
+            .build());
+    }
+
+    /// Arguments for an open market resolving far in the future.
+    pub(crate) fn market_args() -> CreateMarketArgs {
+        CreateMarketArgs {
+            question: "Will test pass?".to_string(),
+            description: "test".to_string(),
+            resolution_time_ns: U64(999_999_999_999_999_999),
+            fee_bps: None,
+            dynamic_fee: None,
+            access_policy: None,
+            limits: None,
+            resolution_priority: None,
+        }
+    }
+
+    /// Create a market for creator.testnet in the current context.
+    pub(crate) fn create_market(
+        contract: &mut MarketContract,
+        args: CreateMarketArgs,
+        deposit: u128,
+    ) -> MarketId {
+        contract.internal_create_market(args, deposit, account("creator.testnet"))
+    }
+
+    pub(crate) fn settle(contract: &mut MarketContract, market_id: MarketId, outcome: Outcome) {
+        let mut market = contract.markets.get(&market_id).unwrap().clone();
+        market.status = MarketStatus::Settled;
+        market.outcome = Some(outcome);
+        contract.markets.insert(market_id, market);
+    }
+
+    /// Market contract owned by owner.testnet, with markets created in order
+    /// by creator.testnet from 100 USDC plus the creation bond. Leaves the
+    /// owner as predecessor at timestamp 0.
+    pub(crate) struct ContractBuilder {
+        governance: Option<GovernanceConfig>,
+        first_market_id: Option<MarketId>,
+        guardian: Option<AccountId>,
+        treasurer: Option<AccountId>,
+        protocol_fee_share_bps: u16,
+        creation_bond: u128,
+        creator_fee_share_bps: u16,
+        curators: Vec<AccountId>,
+        creation_mode: CreationMode,
+        markets: Vec<CreateMarketArgs>,
+    }
+
+    impl ContractBuilder {
+        pub(crate) fn new() -> Self {
+            Self {
+                governance: None,
+                first_market_id: None,
+                guardian: None,
+                treasurer: None,
+                protocol_fee_share_bps: DEFAULT_PROTOCOL_FEE_SHARE_BPS,
+                creation_bond: DEFAULT_CREATION_BOND,
+                creator_fee_share_bps: DEFAULT_CREATOR_FEE_SHARE_BPS,
+                curators: Vec::new(),
+                creation_mode: CreationMode::Open,
+                markets: Vec::new(),
+            }
+        }
+
+        pub(crate) fn governance(mut self, governance: GovernanceConfig) -> Self {
+            self.governance = Some(governance);
+            self
+        }
+
+        pub(crate) fn first_market_id(mut self, market_id: MarketId) -> Self {
+            self.first_market_id = Some(market_id);
+            self
+        }
+
+        pub(crate) fn guardian(mut self, guardian: &str) -> Self {
+            self.guardian = Some(account(guardian));
+            self
+        }
+
+        pub(crate) fn treasurer(mut self, treasurer: &str) -> Self {
+            self.treasurer = Some(account(treasurer));
+            self
+        }
+
+        pub(crate) fn protocol_fee_share_bps(mut self, bps: u16) -> Self {
+            self.protocol_fee_share_bps = bps;
+            self
+        }
+
+        pub(crate) fn creation_bond(mut self, bond: u128) -> Self {
+            self.creation_bond = bond;
+            self
+        }
+
+        pub(crate) fn creator_fee_share_bps(mut self, bps: u16) -> Self {
+            self.creator_fee_share_bps = bps;
+            self
+        }
+
+        pub(crate) fn curators(mut self, curators: &[&str]) -> Self {
+            self.curators = curators.iter().map(|id| account(id)).collect();
+            self
+        }
+
+        pub(crate) fn creation_mode(mut self, mode: CreationMode) -> Self {
+            self.creation_mode = mode;
+            self
+        }
+
+        pub(crate) fn market(self) -> Self {
+            self.market_with(market_args())
+        }
+
+        pub(crate) fn market_with(mut self, args: CreateMarketArgs) -> Self {
+            self.markets.push(args);
+            self
+        }
+
+        pub(crate) fn build(self) -> MarketContract {
+            set_context("owner.testnet", vec![]);
+            let mut contract = MarketContract::new(
+                account("owner.testnet"),
+                account("usdc.testnet"),
+                account("outcome.testnet"),
+                account("oracle.testnet"),
+                self.governance,
+                self.first_market_id,
+            );
+            if let Some(guardian) = self.guardian {
+                contract.guardian = guardian;
+            }
+            if let Some(treasurer) = self.treasurer {
+                contract.treasurer = treasurer;
+            }
+            contract.protocol_fee_share_bps = self.protocol_fee_share_bps;
+            contract.creation_bond = self.creation_bond;
+            contract.creator_fee_share_bps = self.creator_fee_share_bps;
+            contract.curators = self.curators;
+            contract.creation_mode = self.creation_mode;
+
+            // One context per market, so the mints each creation attaches never
+            // add up past the prepaid gas
+            for args in self.markets {
+                set_context("usdc.testnet", vec![]);
+                create_market(&mut contract, args, 100 * USDC_ONE + self.creation_bond);
+            }
+            set_context("owner.testnet", vec![]);
+            contract
+        }
+    }
 }
@@ -0,0 +1,225 @@
+use near_sdk::store::LookupMap;
+use near_sdk::{env, near, AccountId};
+
+use market_types::*;
+
+use crate::{MarketContract, MarketContractExt};
+
+/// Contract state before governance, fees, curation and per-market contracts.
+#[near(serializers = [borsh])]
+struct OldMarketContract {
+    owner: AccountId,
+    usdc_token: AccountId,
+    outcome_token: AccountId,
+    oracle: AccountId,
+    markets: LookupMap<MarketId, OldMarket>,
+    market_count: u64,
+    lp_positions: LookupMap<Vec<u8>, u128>,
+    assertion_to_market: LookupMap<Bytes32, MarketId>,
+}
+
+#[near(serializers = [borsh])]
+struct OldMarket {
+    id: MarketId,
+    question: String,
+    description: String,
+    creator: AccountId,
+    resolution_time_ns: u64,
+    status: MarketStatus,
+    outcome: Option<Outcome>,
+    yes_reserve: u128,
+    no_reserve: u128,
+    total_lp_shares: u128,
+    total_collateral: u128,
+    fee_bps: u16,
+    accrued_fees: u128,
+    assertion_id: Option<Bytes32>,
+    asserted_outcome: Option<Outcome>,
+    resolver: Option<AccountId>,
+    disputer: Option<AccountId>,
+    assertion_submitted_at_ns: Option<u64>,
+    assertion_expires_at_ns: Option<u64>,
+}
+
+impl OldMarket {
+    /// Outcome supply was not tracked per market. Complete sets are backed 1:1
+    /// by collateral, so `total_collateral` bounds either side from above.
+    fn migrate(self, old: &OldMarketContract) -> Market {
+        Market {
+            id: self.id,
+            question: self.question,
+            description: self.description,
+            creator: self.creator,
+            resolution_time_ns: self.resolution_time_ns,
+            status: self.status,
+            outcome: self.outcome,
+            collateral_token: old.usdc_token.clone(),
+            outcome_token: old.outcome_token.clone(),
+            oracle: old.oracle.clone(),
+            yes_reserve: self.yes_reserve,
+            no_reserve: self.no_reserve,
+            total_lp_shares: self.total_lp_shares,
+            total_collateral: self.total_collateral,
+            yes_supply: self.total_collateral,
+            no_supply: self.total_collateral,
+            fee_bps: self.fee_bps,
+            dynamic_fee: None,
+            accrued_fees: self.accrued_fees,
+            protocol_fees: 0,
+            creation_bond: 0,
+            bond_status: BondStatus::NotRequired,
+            bond_slash_reason: None,
+            creator_fee_share_bps: 0,
+            creator_fees: 0,
+            access_policy: AccessPolicy::Open,
+            limits: TradeLimits::default(),
+            resolution_priority: None,
+            assertion_id: self.assertion_id,
+            asserted_outcome: self.asserted_outcome,
+            resolver: self.resolver,
+            disputer: self.disputer,
+            assertion_submitted_at_ns: self.assertion_submitted_at_ns,
+            assertion_expires_at_ns: self.assertion_expires_at_ns,
+            early_assertion: false,
+            assertion_claim: None,
+        }
+    }
+}
+
+#[near]
+impl MarketContract {
+    /// Upgrade from the original single-owner layout. The owner becomes the sole
+    /// approver, treasurer and guardian, new settings take their defaults, and
+    /// every market is rewritten with the contracts it was created with. LP
+    /// positions and assertions keep their storage.
+    #[init(ignore_state)]
+    #[private]
+    pub fn migrate() -> Self {
+        let mut old: OldMarketContract = env::state_read().expect("No state to migrate");
+        let mut this = Self::new(
+            old.owner.clone(),
+            old.usdc_token.clone(),
+            old.outcome_token.clone(),
+            old.oracle.clone(),
+            None,
+            Some(old.market_count),
+        );
+
+        for market_id in 0..old.market_count {
+            let Some(market) = old.markets.remove(&market_id) else {
+                continue;
+            };
+            let market = market.migrate(&old);
+            this.credit_owed(
+                &market.collateral_token,
+                market.total_collateral + market.accrued_fees,
+            );
+            this.markets.set(market_id, Some(market));
+        }
+        // Drop the old entries first; the new ones reuse their keys
+        old.markets.flush();
+        this
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::json_types::U128;
+
+    fn old_market(id: MarketId, status: MarketStatus, collateral: u128) -> OldMarket {
+        OldMarket {
+            id,
+            question: format!("Old market {id}?"),
+            description: String::new(),
+            creator: account("creator.testnet"),
+            resolution_time_ns: 1_000,
+            status,
+            outcome: (status == MarketStatus::Settled).then_some(Outcome::Yes),
+            yes_reserve: collateral / 2,
+            no_reserve: collateral / 2,
+            total_lp_shares: collateral,
+            total_collateral: collateral,
+            fee_bps: 200,
+            accrued_fees: USDC_ONE,
+            assertion_id: None,
+            asserted_outcome: None,
+            resolver: None,
+            disputer: None,
+            assertion_submitted_at_ns: None,
+            assertion_expires_at_ns: None,
+        }
+    }
+
+    fn write_old_state() {
+        set_context("market.testnet", vec![]);
+        let mut markets = LookupMap::new(b"m");
+        markets.insert(0, old_market(0, MarketStatus::Open, 100 * USDC_ONE));
+        markets.insert(1, old_market(1, MarketStatus::Settled, 40 * USDC_ONE));
+        markets.flush();
+        let mut lp_positions = LookupMap::new(b"l");
+        lp_positions.insert(
+            MarketContract::lp_key(0, &account("creator.testnet")),
+            100 * USDC_ONE,
+        );
+        lp_positions.flush();
+        env::state_write(&OldMarketContract {
+            owner: account("owner.testnet"),
+            usdc_token: account("usdc.testnet"),
+            outcome_token: account("outcome.testnet"),
+            oracle: account("oracle.testnet"),
+            markets,
+            market_count: 2,
+            lp_positions,
+            assertion_to_market: LookupMap::new(b"a"),
+        });
+    }
+
+    #[test]
+    fn migrate_reads_baseline_state() {
+        write_old_state();
+        let contract = MarketContract::migrate();
+
+        let config = contract.get_config();
+        assert_eq!(config.owner, account("owner.testnet"));
+        assert_eq!(config.treasurer, account("owner.testnet"));
+        assert_eq!(contract.get_market_count(), 2);
+        assert_eq!(
+            contract.get_lp_shares(0, account("creator.testnet")),
+            U128(100 * USDC_ONE)
+        );
+
+        let market = contract.markets.get(&1).unwrap();
+        assert_eq!(market.status, MarketStatus::Settled);
+        assert_eq!(market.collateral_token, account("usdc.testnet"));
+        assert_eq!(market.outcome_token, account("outcome.testnet"));
+        assert_eq!(market.oracle, account("oracle.testnet"));
+        assert_eq!(
+            contract.get_total_owed(account("usdc.testnet")).0,
+            142 * USDC_ONE
+        );
+
+        let report = contract.check_invariants(0);
+        assert!(report.collateral_covers_supply && report.owed_covers_market);
+    }
+
+    #[test]
+    fn migrated_market_keeps_trading() {
+        write_old_state();
+        let mut contract = MarketContract::migrate();
+
+        set_context("usdc.testnet", vec![]);
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+        set_context("buyer.testnet", vec![]);
+        contract.sell(0, Outcome::Yes, U128(USDC_ONE), U128(0));
+        assert!(contract.check_invariants(0).owed_covers_market);
+    }
+}
@@ -0,0 +1,170 @@
+use near_sdk::json_types::U128;
+use near_sdk::{env, near, require, AccountId, Gas, GasWeight, NearToken, Promise, PromiseResult};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt, GAS_FOR_MINT, GAS_FOR_MINT_CALLBACK};
+
+/// Outcome tokens minted to a user.
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub struct UserMint {
+    pub market_id: MarketId,
+    pub outcome: Outcome,
+    pub amount: U128,
+}
+
+#[near]
+impl MarketContract {
+    /// Outcome tokens owed to `account_id` because minting them failed, e.g.
+    /// while the account was not registered on the outcome token.
+    pub fn get_unminted(
+        &self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_id: AccountId,
+    ) -> U128 {
+        let key = Self::unminted_key(market_id, &account_id, outcome);
+        U128(self.unminted.get(&key).copied().unwrap_or(0))
+    }
+
+    /// Mint the caller's owed outcome tokens. Register on the outcome token
+    /// with `storage_deposit` first.
+    pub fn claim_unminted(&mut self, market_id: MarketId, outcome: Outcome) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .unminted
+            .remove(&Self::unminted_key(market_id, &account_id, outcome))
+            .unwrap_or(0);
+        require!(amount > 0, "No unminted tokens to claim");
+
+        let outcome_token = self
+            .markets
+            .get(&market_id)
+            .expect("Market not found")
+            .outcome_token
+            .clone();
+        self.mint_to(
+            outcome_token,
+            &account_id,
+            vec![UserMint {
+                market_id,
+                outcome,
+                amount: U128(amount),
+            }],
+            GAS_FOR_MINT,
+        )
+    }
+
+    /// Keep the tokens of a failed mint batch as unminted, claimable tokens.
+    #[private]
+    pub fn on_mint_to(&mut self, account_id: AccountId, mints: Vec<UserMint>) {
+        self.track_pending_mints(&account_id, &mints, false);
+        if matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            return;
+        }
+        self.defer_mints(&account_id, mints);
+    }
+}
+
+impl MarketContract {
+    /// Record outcome tokens owed to `account_id` for `claim_unminted`.
+    pub(crate) fn defer_mints(&mut self, account_id: &AccountId, mints: Vec<UserMint>) {
+        for mint in mints {
+            let key = Self::unminted_key(mint.market_id, account_id, mint.outcome);
+            let owed = self.unminted.get(&key).copied().unwrap_or(0);
+            self.unminted.insert(key, owed + mint.amount.0);
+
+            Event::MintDeferred {
+                market_id: mint.market_id,
+                user: account_id,
+                outcome: mint.outcome,
+                amount: mint.amount,
+            }
+            .emit();
+        }
+    }
+
+    /// Mint outcome tokens to a user in one batch, checked by `on_mint_to`.
+    /// Each mint gets `gas_per_mint` plus an even share of the gas left over.
+    pub(crate) fn mint_to(
+        &mut self,
+        outcome_token: AccountId,
+        account_id: &AccountId,
+        mints: Vec<UserMint>,
+        gas_per_mint: Gas,
+    ) -> Promise {
+        self.track_pending_mints(account_id, &mints, true);
+        let batch = mints
+            .iter()
+            .fold(Promise::new(outcome_token), |batch, mint| {
+                batch.function_call_weight(
+                    "mint".to_string(),
+                    near_sdk::serde_json::json!({
+                        "market_id": mint.market_id,
+                        "outcome": mint.outcome,
+                        "account_id": account_id,
+                        "amount": mint.amount,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    gas_per_mint,
+                    GasWeight(1),
+                )
+            });
+        batch.then(
+            Promise::new(env::current_account_id()).function_call(
+                "on_mint_to".to_string(),
+                near_sdk::serde_json::json!({
+                    "account_id": account_id,
+                    "mints": mints,
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_MINT_CALLBACK,
+            ),
+        )
+    }
+
+    /// Outcome tokens being minted to `account_id`, tracked for markets with a
+    /// holding cap so in-flight buys count towards it.
+    pub(crate) fn pending_mint(
+        &self,
+        market_id: MarketId,
+        account_id: &AccountId,
+        outcome: Outcome,
+    ) -> u128 {
+        let key = Self::unminted_key(market_id, account_id, outcome);
+        self.pending_mints.get(&key).copied().unwrap_or(0)
+    }
+
+    fn track_pending_mints(&mut self, account_id: &AccountId, mints: &[UserMint], started: bool) {
+        for mint in mints {
+            let capped = self
+                .markets
+                .get(&mint.market_id)
+                .is_some_and(|market| market.limits.max_holding.is_some());
+            if !capped {
+                continue;
+            }
+            let key = Self::unminted_key(mint.market_id, account_id, mint.outcome);
+            let pending = self.pending_mints.get(&key).copied().unwrap_or(0);
+            if started {
+                self.pending_mints.insert(key, pending + mint.amount.0);
+            } else if pending > mint.amount.0 {
+                self.pending_mints.insert(key, pending - mint.amount.0);
+            } else {
+                self.pending_mints.remove(&key);
+            }
+        }
+    }
+
+    fn unminted_key(market_id: MarketId, account_id: &AccountId, outcome: Outcome) -> Vec<u8> {
+        let mut key = Self::lp_key(market_id, account_id);
+        key.push(outcome.as_bool() as u8);
+        key
+    }
+}
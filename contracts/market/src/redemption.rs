@@ -1,19 +1,30 @@
 use near_sdk::json_types::U128;
-use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise};
+use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseResult};
 
 use market_types::*;
 
 use crate::events::Event;
-use crate::{MarketContract, MarketContractExt};
+use crate::mints::UserMint;
+use crate::{MarketContract, MarketContractExt, GAS_FOR_MINT_CALLBACK};
 
 const GAS_FOR_BURN: Gas = Gas::from_tgas(20);
 const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(30);
 const GAS_FOR_REDEEM_CALLBACK: Gas = Gas::from_tgas(40);
+const GAS_FOR_BURN_BATCH: Gas = Gas::from_tgas(50);
+/// Own execution of the batch redemption callbacks, excluding the calls they schedule
+const GAS_FOR_REDEEM_ALL_CALLBACK: Gas = Gas::from_tgas(30);
+const GAS_FOR_REDEEM_ALL_PAYOUT_CALLBACK: Gas = Gas::from_tgas(30);
+const GAS_FOR_REMINT: Gas = Gas::from_tgas(4);
+const GAS_FOR_CLEAR_CALLBACK: Gas = Gas::from_tgas(60);
 
 #[near]
 impl MarketContract {
     /// Redeem winning outcome tokens for USDC 1:1 after market settlement.
-    pub fn redeem_tokens(&mut self, market_id: MarketId, amount: U128) {
+    /// Without `amount`, the caller's whole winning balance is redeemed.
+    pub fn redeem_tokens(&mut self, market_id: MarketId, amount: Option<U128>) -> Promise {
+        let Some(amount) = amount else {
+            return self.redeem_all(vec![market_id], None);
+        };
         let redeemer = env::predecessor_account_id();
         let amount = amount.0;
         require!(amount > 0, "Amount must be greater than 0");
@@ -26,7 +37,7 @@ impl MarketContract {
         let winning_outcome = market.outcome.expect("Settled market must have outcome");
 
         // Burn winning tokens from redeemer, then transfer USDC
-        Promise::new(self.outcome_token.clone())
+        Promise::new(market.outcome_token.clone())
             .function_call(
                 "burn".to_string(),
                 near_sdk::serde_json::json!({
@@ -41,20 +52,19 @@ impl MarketContract {
                 GAS_FOR_BURN,
             )
             .then(
-                Promise::new(env::current_account_id())
-                    .function_call(
-                        "on_redeem_burn_complete".to_string(),
-                        near_sdk::serde_json::json!({
-                            "market_id": market_id,
-                            "redeemer": redeemer,
-                            "amount": U128(amount),
-                        })
-                        .to_string()
-                        .into_bytes(),
-                        NearToken::from_yoctonear(0),
-                        GAS_FOR_REDEEM_CALLBACK,
-                    )
-            );
+                Promise::new(env::current_account_id()).function_call(
+                    "on_redeem_burn_complete".to_string(),
+                    near_sdk::serde_json::json!({
+                        "market_id": market_id,
+                        "redeemer": redeemer,
+                        "amount": U128(amount),
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_REDEEM_CALLBACK,
+                ),
+            )
     }
 
     #[private]
@@ -72,7 +82,15 @@ impl MarketContract {
 
         match env::promise_result(0) {
             near_sdk::PromiseResult::Successful(_) => {
-                // Burn succeeded, transfer USDC to redeemer
+                // Burn succeeded, pay out of the market's collateral. The tokens
+                // are gone, so a market that cannot cover them mints them back.
+                let Some(market) = self.internal_apply_redemption(market_id, amount.0) else {
+                    self.remint_redeemed(&redeemer, vec![market_id], vec![amount]);
+                    return;
+                };
+                let collateral_token = market.collateral_token;
+                self.debit_owed(&collateral_token, amount.0);
+
                 Event::Redeemed {
                     market_id,
                     user: &redeemer,
@@ -80,22 +98,577 @@ impl MarketContract {
                 }
                 .emit();
 
-                Promise::new(self.usdc_token.clone())
-                    .function_call(
-                        "ft_transfer".to_string(),
-                        near_sdk::serde_json::json!({
-                            "receiver_id": redeemer,
-                            "amount": amount,
-                        })
-                        .to_string()
-                        .into_bytes(),
-                        NearToken::from_yoctonear(1),
-                        GAS_FOR_FT_TRANSFER,
-                    );
+                Promise::new(collateral_token).function_call(
+                    "ft_transfer".to_string(),
+                    near_sdk::serde_json::json!({
+                        "receiver_id": redeemer,
+                        "amount": amount,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(1),
+                    GAS_FOR_FT_TRANSFER,
+                );
             }
             _ => {
                 env::panic_str("Token burn failed, cannot redeem");
             }
         }
     }
+
+    /// Redeem winning tokens in several settled markets with one batched burn
+    /// and a single collateral transfer. Without `amounts`, each market's whole
+    /// winning balance is redeemed. Entries whose burn fails (e.g. the balance
+    /// is too low) are skipped and left untouched. Returns the amount redeemed
+    /// per market.
+    pub fn redeem_all(&mut self, market_ids: Vec<MarketId>, amounts: Option<Vec<U128>>) -> Promise {
+        let redeemer = env::predecessor_account_id();
+        if let Some(amounts) = &amounts {
+            require!(
+                market_ids.len() == amounts.len(),
+                "Each market needs an amount"
+            );
+            require!(
+                amounts.iter().all(|amount| amount.0 > 0),
+                "Amount must be greater than 0"
+            );
+        }
+
+        let (outcome_token, winning) = self.assert_settled_batch(&market_ids);
+        let len = market_ids.len();
+        let burns = market_ids
+            .iter()
+            .zip(winning)
+            .enumerate()
+            .map(|(i, (&market_id, outcome))| OutcomeBurn {
+                market_id,
+                outcome,
+                amount: amounts.as_ref().map(|amounts| amounts[i]),
+            })
+            .collect();
+
+        Self::burn_batch_then(
+            outcome_token,
+            redeemer,
+            burns,
+            market_ids,
+            "on_redeem_all_burned",
+            redeem_all_callback_gas(len),
+        )
+    }
+
+    /// Burn the caller's losing tokens in settled markets. They are worth
+    /// nothing, and clearing them frees their storage on the outcome token,
+    /// which refunds the NEAR it held from the caller's storage deposit there.
+    /// Returns the amount burned per market.
+    pub fn clear_losing_tokens(&mut self, market_ids: Vec<MarketId>) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let (outcome_token, winning) = self.assert_settled_batch(&market_ids);
+        let burns = market_ids
+            .iter()
+            .zip(winning)
+            .map(|(&market_id, outcome)| OutcomeBurn {
+                market_id,
+                outcome: outcome.opposite(),
+                amount: None,
+            })
+            .collect();
+
+        Self::burn_batch_then(
+            outcome_token,
+            account_id,
+            burns,
+            market_ids,
+            "on_losing_tokens_cleared",
+            GAS_FOR_CLEAR_CALLBACK,
+        )
+    }
+
+    #[private]
+    pub fn on_losing_tokens_cleared(
+        &mut self,
+        account_id: AccountId,
+        market_ids: Vec<MarketId>,
+    ) -> Vec<U128> {
+        let burned = Self::burn_batch_result(market_ids.len());
+        for (&market_id, amount) in market_ids.iter().zip(&burned) {
+            if amount.0 == 0 {
+                continue;
+            }
+            let mut market = self
+                .markets
+                .get(&market_id)
+                .expect("Market not found")
+                .clone();
+            // Saturates: the tokens are already burned, so this must not panic
+            match market.outcome.expect("Settled market must have outcome") {
+                Outcome::Yes => market.no_supply = market.no_supply.saturating_sub(amount.0),
+                Outcome::No => market.yes_supply = market.yes_supply.saturating_sub(amount.0),
+            }
+            self.markets.insert(market_id, market);
+
+            Event::LosingTokensCleared {
+                market_id,
+                user: &account_id,
+                amount: *amount,
+            }
+            .emit();
+        }
+        burned
+    }
+
+    /// Pay out every market whose burn succeeded with one `ft_transfer`. Never
+    /// panics once tokens are burned: a market that cannot cover its burn is
+    /// skipped and its tokens are recorded for `claim_unminted`.
+    #[private]
+    pub fn on_redeem_all_burned(
+        &mut self,
+        account_id: AccountId,
+        market_ids: Vec<MarketId>,
+    ) -> Vec<U128> {
+        let mut burned = Self::burn_batch_result(market_ids.len());
+
+        let mut collateral_token = None;
+        let mut total = 0u128;
+        let mut skipped = (Vec::new(), Vec::new());
+        for (&market_id, amount) in market_ids.iter().zip(burned.iter_mut()) {
+            if amount.0 == 0 {
+                continue;
+            }
+            let Some(market) = self.internal_apply_redemption(market_id, amount.0) else {
+                skipped.0.push(market_id);
+                skipped.1.push(*amount);
+                *amount = U128(0);
+                continue;
+            };
+            collateral_token.get_or_insert(market.collateral_token);
+            total += amount.0;
+
+            Event::Redeemed {
+                market_id,
+                user: &account_id,
+                collateral_out: *amount,
+            }
+            .emit();
+        }
+
+        // Recorded here rather than re-minted, so this callback's gas does not
+        // depend on how many markets were skipped
+        let (_, skipped) = self.redeemed_mints(&skipped.0, &skipped.1);
+        self.defer_mints(&account_id, skipped);
+        let Some(collateral_token) = collateral_token else {
+            return burned;
+        };
+        self.debit_owed(&collateral_token, total);
+
+        Promise::new(collateral_token.clone())
+            .function_call(
+                "ft_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "receiver_id": account_id.clone(),
+                    "amount": U128(total),
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(1),
+                GAS_FOR_FT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_redeem_all_paid".to_string(),
+                    near_sdk::serde_json::json!({
+                        "redeemer": account_id,
+                        "market_ids": market_ids,
+                        "amounts": burned,
+                        "token": collateral_token,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    redeem_all_paid_gas(market_ids.len()),
+                ),
+            );
+        burned
+    }
+
+    /// Undo a batch redemption whose transfer failed: restore each market and
+    /// mint the burned winning tokens back so they can be redeemed again.
+    #[private]
+    pub fn on_redeem_all_paid(
+        &mut self,
+        redeemer: AccountId,
+        market_ids: Vec<MarketId>,
+        amounts: Vec<U128>,
+        token: AccountId,
+    ) {
+        if matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            return;
+        }
+
+        let mut total = 0u128;
+        for (&market_id, amount) in market_ids.iter().zip(&amounts) {
+            if amount.0 == 0 {
+                continue;
+            }
+            let mut market = self
+                .markets
+                .get(&market_id)
+                .expect("Market not found")
+                .clone();
+            let winning_outcome = market.outcome.expect("Settled market must have outcome");
+            market.total_collateral += amount.0;
+            match winning_outcome {
+                Outcome::Yes => market.yes_supply += amount.0,
+                Outcome::No => market.no_supply += amount.0,
+            }
+            total += amount.0;
+            self.markets.insert(market_id, market);
+        }
+        self.credit_owed(&token, total);
+        self.remint_redeemed(&redeemer, market_ids, amounts);
+
+        env::log_str(&format!(
+            "Batch redemption of {total} for {redeemer} failed — minting winning tokens back"
+        ));
+    }
+}
+
+impl MarketContract {
+    /// Check a batch of distinct settled markets sharing one outcome token and
+    /// collateral token. Returns the outcome token and each winning outcome.
+    fn assert_settled_batch(&self, market_ids: &[MarketId]) -> (AccountId, Vec<Outcome>) {
+        require!(
+            !market_ids.is_empty() && market_ids.len() <= MAX_BATCH_REDEMPTIONS,
+            format!("Batch between 1 and {MAX_BATCH_REDEMPTIONS} markets at once")
+        );
+        let mut tokens: Option<(AccountId, AccountId)> = None;
+        let mut winning = Vec::with_capacity(market_ids.len());
+        for (i, market_id) in market_ids.iter().enumerate() {
+            require!(
+                !market_ids[..i].contains(market_id),
+                "Duplicate market in batch"
+            );
+            let market = self.markets.get(market_id).expect("Market not found");
+            require!(
+                market.status == MarketStatus::Settled,
+                "Market is not settled"
+            );
+            let market_tokens = (
+                market.outcome_token.clone(),
+                market.collateral_token.clone(),
+            );
+            require!(
+                tokens.get_or_insert_with(|| market_tokens.clone()) == &market_tokens,
+                "Batched markets must share an outcome token and collateral token"
+            );
+            winning.push(market.outcome.expect("Settled market must have outcome"));
+        }
+        let (outcome_token, _) = tokens.expect("Batch is not empty");
+        (outcome_token, winning)
+    }
+
+    fn burn_batch_then(
+        outcome_token: AccountId,
+        account_id: AccountId,
+        burns: Vec<OutcomeBurn>,
+        market_ids: Vec<MarketId>,
+        callback: &str,
+        callback_gas: Gas,
+    ) -> Promise {
+        Promise::new(outcome_token)
+            .function_call(
+                "burn_batch".to_string(),
+                near_sdk::serde_json::json!({
+                    "account_id": account_id.clone(),
+                    "burns": burns,
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_BURN_BATCH,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    callback.to_string(),
+                    near_sdk::serde_json::json!({
+                        "account_id": account_id,
+                        "market_ids": market_ids,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    callback_gas,
+                ),
+            )
+    }
+
+    /// Amounts burned by the preceding `burn_batch`, one per market.
+    fn burn_batch_result(len: usize) -> Vec<U128> {
+        let burned = match env::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                near_sdk::serde_json::from_slice::<Vec<U128>>(&bytes)
+                    .expect("Invalid burn_batch result")
+            }
+            _ => env::panic_str("Token burn failed"),
+        };
+        require!(
+            burned.len() == len,
+            "burn_batch result does not match the batch"
+        );
+        burned
+    }
+
+    /// Take `amount` of a settled market's collateral for burned winning tokens.
+    /// Returns `None` and leaves the market untouched if it cannot cover them.
+    fn internal_apply_redemption(&mut self, market_id: MarketId, amount: u128) -> Option<Market> {
+        let mut market = self.markets.get(&market_id)?.clone();
+        market.total_collateral = market.total_collateral.checked_sub(amount)?;
+        let supply = match market.outcome? {
+            Outcome::Yes => &mut market.yes_supply,
+            Outcome::No => &mut market.no_supply,
+        };
+        *supply = supply.checked_sub(amount)?;
+        self.markets.insert(market_id, market.clone());
+        Some(market)
+    }
+
+    /// Mint burned winning tokens back to `account_id` in one batch. Mints that
+    /// fail stay claimable through `claim_unminted`.
+    fn remint_redeemed(
+        &mut self,
+        account_id: &AccountId,
+        market_ids: Vec<MarketId>,
+        amounts: Vec<U128>,
+    ) {
+        if let (Some(outcome_token), mints) = self.redeemed_mints(&market_ids, &amounts) {
+            self.mint_to(outcome_token, account_id, mints, GAS_FOR_REMINT);
+        }
+    }
+
+    /// Winning tokens to give back for burned `amounts`, and their outcome token.
+    fn redeemed_mints(
+        &self,
+        market_ids: &[MarketId],
+        amounts: &[U128],
+    ) -> (Option<AccountId>, Vec<UserMint>) {
+        let mut outcome_token = None;
+        let mut mints = Vec::new();
+        for (&market_id, &amount) in market_ids.iter().zip(amounts) {
+            let Some(market) = self.markets.get(&market_id) else {
+                continue;
+            };
+            let Some(winning_outcome) = market.outcome else {
+                continue;
+            };
+            if amount.0 == 0 {
+                continue;
+            }
+            outcome_token.get_or_insert_with(|| market.outcome_token.clone());
+            mints.push(UserMint {
+                market_id,
+                outcome: winning_outcome,
+                amount,
+            });
+        }
+        (outcome_token, mints)
+    }
+}
+
+/// Gas for `on_redeem_all_paid`, which may re-mint every market in the batch.
+fn redeem_all_paid_gas(markets: usize) -> Gas {
+    GAS_FOR_REDEEM_ALL_PAYOUT_CALLBACK
+        .saturating_add(GAS_FOR_REMINT.saturating_mul(markets as u64))
+        .saturating_add(GAS_FOR_MINT_CALLBACK)
+}
+
+/// Gas for `on_redeem_all_burned`, including the transfer and `on_redeem_all_paid`
+/// it schedules.
+fn redeem_all_callback_gas(markets: usize) -> Gas {
+    GAS_FOR_REDEEM_ALL_CALLBACK
+        .saturating_add(GAS_FOR_FT_TRANSFER)
+        .saturating_add(redeem_all_paid_gas(markets))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::get_created_receipts;
+
+    /// Two markets settled YES, each backed by 100 USDC of collateral.
+    fn settled_contract() -> MarketContract {
+        let mut contract = ContractBuilder::new().market().market().build();
+        for market_id in 0..2 {
+            settle(&mut contract, market_id, Outcome::Yes);
+        }
+        contract
+    }
+
+    fn burned(amounts: &[u128]) -> PromiseResult {
+        let amounts: Vec<U128> = amounts.iter().copied().map(U128).collect();
+        PromiseResult::Successful(near_sdk::serde_json::to_vec(&amounts).unwrap())
+    }
+
+    #[test]
+    fn redeem_all_pays_markets_whose_burn_succeeded() {
+        let mut contract = settled_contract();
+        let owed_before = contract.get_total_owed(account("usdc.testnet")).0;
+
+        set_context("market.testnet", vec![burned(&[10 * USDC_ONE, 0])]);
+        let redeemed = contract.on_redeem_all_burned(account("alice.testnet"), vec![0, 1]);
+
+        assert_eq!(redeemed, vec![U128(10 * USDC_ONE), U128(0)]);
+        assert_eq!(
+            contract.markets.get(&0).unwrap().total_collateral,
+            90 * USDC_ONE
+        );
+        assert_eq!(
+            contract.markets.get(&1).unwrap().total_collateral,
+            100 * USDC_ONE
+        );
+        assert_eq!(
+            contract.get_total_owed(account("usdc.testnet")).0,
+            owed_before - 10 * USDC_ONE
+        );
+    }
+
+    #[test]
+    fn uncovered_burn_is_skipped_instead_of_panicking() {
+        let mut contract = settled_contract();
+
+        set_context(
+            "market.testnet",
+            vec![burned(&[10 * USDC_ONE, 500 * USDC_ONE])],
+        );
+        let redeemed = contract.on_redeem_all_burned(account("alice.testnet"), vec![0, 1]);
+
+        assert_eq!(redeemed, vec![U128(10 * USDC_ONE), U128(0)]);
+        assert_eq!(
+            contract.markets.get(&1).unwrap().total_collateral,
+            100 * USDC_ONE
+        );
+        assert_eq!(
+            contract.get_unminted(1, Outcome::Yes, account("alice.testnet")),
+            U128(500 * USDC_ONE)
+        );
+    }
+
+    /// Static gas attached to the receipts created in the current context.
+    fn attached_gas() -> Gas {
+        get_created_receipts()
+            .iter()
+            .flat_map(|receipt| &receipt.actions)
+            .map(|action| match action {
+                MockAction::FunctionCallWeight { prepaid_gas, .. } => *prepaid_gas,
+                _ => Gas::from_gas(0),
+            })
+            .fold(Gas::from_gas(0), Gas::saturating_add)
+    }
+
+    #[test]
+    fn redeem_all_gas_covers_the_largest_batch() {
+        let mut builder = ContractBuilder::new();
+        for _ in 0..MAX_BATCH_REDEMPTIONS {
+            builder = builder.market();
+        }
+        let mut contract = builder.build();
+        let market_ids: Vec<MarketId> = (0..MAX_BATCH_REDEMPTIONS as u64).collect();
+        for &market_id in &market_ids {
+            settle(&mut contract, market_id, Outcome::Yes);
+        }
+        let amounts = vec![U128(USDC_ONE); MAX_BATCH_REDEMPTIONS];
+
+        // The burn and every callback after it fit in one transaction, leaving
+        // 20 Tgas for redeem_all itself
+        set_context("alice.testnet", vec![]);
+        contract.redeem_all(market_ids.clone(), Some(amounts.clone()));
+        assert!(attached_gas() <= Gas::from_tgas(280));
+
+        // Each callback keeps its own share after attaching gas to what it schedules
+        set_context(
+            "market.testnet",
+            vec![burned(&[USDC_ONE; MAX_BATCH_REDEMPTIONS])],
+        );
+        contract.on_redeem_all_burned(account("alice.testnet"), market_ids.clone());
+        assert!(
+            attached_gas().saturating_add(GAS_FOR_REDEEM_ALL_CALLBACK)
+                <= redeem_all_callback_gas(MAX_BATCH_REDEMPTIONS)
+        );
+
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+        contract.on_redeem_all_paid(
+            account("alice.testnet"),
+            market_ids,
+            amounts,
+            account("usdc.testnet"),
+        );
+        assert!(
+            attached_gas().saturating_add(GAS_FOR_REDEEM_ALL_PAYOUT_CALLBACK)
+                <= redeem_all_paid_gas(MAX_BATCH_REDEMPTIONS)
+        );
+    }
+
+    #[test]
+    fn failed_batch_payout_restores_markets() {
+        let mut contract = settled_contract();
+        let owed_before = contract.get_total_owed(account("usdc.testnet")).0;
+        set_context(
+            "market.testnet",
+            vec![burned(&[10 * USDC_ONE, 5 * USDC_ONE])],
+        );
+        contract.on_redeem_all_burned(account("alice.testnet"), vec![0, 1]);
+
+        set_context("market.testnet", vec![PromiseResult::Failed]);
+        contract.on_redeem_all_paid(
+            account("alice.testnet"),
+            vec![0, 1],
+            vec![U128(10 * USDC_ONE), U128(5 * USDC_ONE)],
+            account("usdc.testnet"),
+        );
+        for market_id in 0..2 {
+            let market = contract.markets.get(&market_id).unwrap();
+            assert_eq!(market.total_collateral, 100 * USDC_ONE);
+            assert_eq!(market.yes_supply, 50 * USDC_ONE);
+        }
+        assert_eq!(
+            contract.get_total_owed(account("usdc.testnet")).0,
+            owed_before
+        );
+    }
+
+    #[test]
+    fn clearing_losing_tokens_reduces_losing_supply() {
+        let mut contract = settled_contract();
+        set_context("market.testnet", vec![burned(&[3 * USDC_ONE, 0])]);
+        let cleared = contract.on_losing_tokens_cleared(account("alice.testnet"), vec![0, 1]);
+
+        assert_eq!(cleared, vec![U128(3 * USDC_ONE), U128(0)]);
+        let market = contract.markets.get(&0).unwrap();
+        assert_eq!(market.no_supply, 47 * USDC_ONE);
+        assert_eq!(market.yes_supply, 50 * USDC_ONE);
+        assert_eq!(market.total_collateral, 100 * USDC_ONE);
+        let logs = near_sdk::test_utils::get_logs();
+        assert!(logs[0].contains("\"event\":\"losing_tokens_cleared\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Market is not settled")]
+    fn redeem_all_requires_settled_markets() {
+        let mut contract = settled_contract();
+        let mut market = contract.markets.get(&1).unwrap().clone();
+        market.status = MarketStatus::Open;
+        contract.markets.insert(1, market);
+
+        set_context("alice.testnet", vec![]);
+        contract.redeem_all(vec![0, 1], Some(vec![U128(USDC_ONE), U128(USDC_ONE)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate market in batch")]
+    fn redeem_all_rejects_duplicates() {
+        let mut contract = settled_contract();
+        set_context("alice.testnet", vec![]);
+        contract.redeem_all(vec![0, 0], None);
+    }
 }
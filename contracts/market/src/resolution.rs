@@ -8,14 +8,19 @@ use crate::{MarketContract, MarketContractExt};
 
 const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas::from_tgas(100);
 const GAS_FOR_RESOLUTION_CALLBACK: Gas = Gas::from_tgas(15);
+const GAS_FOR_SNAPSHOT: Gas = Gas::from_tgas(10);
 const DEFAULT_ORACLE_LIVENESS_NS: u64 = 2 * 60 * 60 * 1_000_000_000;
+/// Assertions made before the resolution time stay disputable for longer
+const EARLY_ORACLE_LIVENESS_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
 const DEFAULT_IDENTIFIER: Bytes32 = *b"ASSERT_TRUTH\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 
 #[near]
 impl MarketContract {
     // ── Submit Resolution ──────────────────────────────────────────────
     // Called via ft_transfer_call with SubmitResolution message.
-    // The attached USDC is the oracle bond.
+    // The attached USDC is the oracle bond. Before the resolution time an
+    // open market can still be resolved early with a larger bond, which halts
+    // trading until the oracle settles it.
 
     pub(crate) fn internal_submit_resolution(
         &mut self,
@@ -30,10 +35,31 @@ impl MarketContract {
             market.status == MarketStatus::Open || market.status == MarketStatus::Closed,
             "Market cannot be resolved in current status"
         );
-        require!(
-            now >= market.resolution_time_ns,
-            "Resolution time has not passed yet"
-        );
+        let early = now < market.resolution_time_ns;
+        if early {
+            require!(
+                market.status == MarketStatus::Open,
+                "Market cannot be resolved in current status"
+            );
+            require!(
+                bond_amount >= self.early_resolution_bond,
+                format!(
+                    "Early resolution requires a bond of at least {}",
+                    self.early_resolution_bond
+                )
+            );
+        }
+        let liveness_ns = if early {
+            EARLY_ORACLE_LIVENESS_NS
+        } else {
+            DEFAULT_ORACLE_LIVENESS_NS
+        };
+        if market.priority_window_end_ns().is_some_and(|end| now < end) {
+            require!(
+                market.priority_resolvers().contains(&resolver),
+                "Only the creator or designated resolver can resolve during the priority window"
+            );
+        }
 
         let mut market = market.clone();
         let previous_status = market.status;
@@ -42,55 +68,59 @@ impl MarketContract {
         market.resolver = Some(resolver.clone());
         market.disputer = None;
         market.assertion_submitted_at_ns = Some(now);
-        market.assertion_expires_at_ns = Some(now + DEFAULT_ORACLE_LIVENESS_NS);
-
-        // Build claim: keccak256("market:{id}:outcome:{yes/no}")
-        let outcome_str = match outcome {
-            Outcome::Yes => "yes",
-            Outcome::No => "no",
+        market.assertion_expires_at_ns = Some(now + liveness_ns);
+        market.early_assertion = early;
+
+        // The oracle asserts keccak256 of the readable claim it is also sent
+        let claim = ResolutionClaim {
+            market_contract: env::current_account_id(),
+            market_id: U64(market_id),
+            question: market.question.clone(),
+            resolution_criteria: market.description.clone(),
+            resolution_time_ns: U64(market.resolution_time_ns),
+            asserted_outcome: outcome,
         };
-        let claim_str = format!("market:{}:outcome:{}", market_id, outcome_str);
-        let claim: Bytes32 = env::keccak256(claim_str.as_bytes())
-            .try_into()
-            .expect("keccak256 produces 32 bytes");
+        let claim_data = claim.preimage();
+        let claim_hash = Self::claim_hash(&claim_data);
+        market.assertion_claim = Some(claim);
 
         if let Some(existing_assertion) = market.assertion_id {
             self.assertion_to_market.remove(&existing_assertion);
         }
 
         let assertion_id = Self::compute_assertion_id(
-            claim,
+            claim_hash,
             bond_amount,
             now,
-            DEFAULT_ORACLE_LIVENESS_NS,
-            &self.usdc_token,
-            &Some(env::current_account_id()),
-            &DEFAULT_IDENTIFIER,
-            &env::current_account_id(),
+            liveness_ns,
+            &market.collateral_token,
         );
         let assertion_id_hex = hex::encode(assertion_id);
         market.assertion_id = Some(assertion_id);
         self.assertion_to_market.insert(assertion_id, market_id);
 
+        let collateral_token = market.collateral_token.clone();
+        let oracle = market.oracle.clone();
         self.markets.insert(market_id, market);
 
         // Forward bond to oracle via ft_transfer_call
         let oracle_msg = near_sdk::serde_json::json!({
             "action": "AssertTruth",
-            "claim": claim,
+            "claim": claim_hash,
+            "claim_data": claim_data,
             "asserter": resolver.to_string(),
             "callback_recipient": env::current_account_id().to_string(),
-            "liveness_ns": U64(DEFAULT_ORACLE_LIVENESS_NS),
+            "liveness_ns": U64(liveness_ns),
             "identifier": DEFAULT_IDENTIFIER,
             "assertion_time_ns": U64(now),
             "assertion_id_override": assertion_id,
         });
 
-        Promise::new(self.usdc_token.clone())
+        Promise::new(collateral_token)
             .function_call(
                 "ft_transfer_call".to_string(),
                 near_sdk::serde_json::json!({
-                    "receiver_id": self.oracle.to_string(),
+                    "receiver_id": oracle.to_string(),
                     "amount": U128(bond_amount),
                     "msg": oracle_msg.to_string(),
                 })
@@ -146,11 +176,19 @@ impl MarketContract {
             market.resolver = None;
             market.assertion_submitted_at_ns = None;
             market.assertion_expires_at_ns = None;
+            market.early_assertion = false;
+            market.assertion_claim = None;
             self.markets.insert(market_id, market);
 
             env::log_str(&format!(
                 "Resolution submission failed for market {market_id} — state rolled back"
             ));
+            Event::ResolutionSubmissionFailed {
+                market_id,
+                assertion_id: &assertion_id,
+                restored_status: previous_status,
+            }
+            .emit();
             return;
         }
 
@@ -166,11 +204,6 @@ impl MarketContract {
     // ── Oracle Callbacks ───────────────────────────────────────────────
 
     pub fn assertion_resolved_callback(&mut self, assertion_id: String, asserted_truthfully: bool) {
-        require!(
-            env::predecessor_account_id() == self.oracle,
-            "Only oracle can call this callback"
-        );
-
         let assertion_bytes: Bytes32 = hex::decode(&assertion_id)
             .expect("Invalid assertion_id hex")
             .try_into()
@@ -183,6 +216,11 @@ impl MarketContract {
             .expect("No market found for this assertion");
 
         let market = self.markets.get(&market_id).expect("Market not found");
+        // Only the oracle the market was created with can settle it
+        require!(
+            env::predecessor_account_id() == market.oracle,
+            "Only oracle can call this callback"
+        );
         let mut market = market.clone();
 
         if asserted_truthfully {
@@ -193,6 +231,7 @@ impl MarketContract {
             market.asserted_outcome = None;
             market.assertion_submitted_at_ns = None;
             market.assertion_expires_at_ns = None;
+            market.early_assertion = false;
             self.assertion_to_market.remove(&assertion_bytes);
 
             Event::MarketSettled {
@@ -200,15 +239,39 @@ impl MarketContract {
                 outcome: market.outcome.unwrap(),
             }
             .emit();
+
+            // Freeze outcome balances for off-chain verification of redemptions
+            Promise::new(market.outcome_token.clone()).function_call(
+                "snapshot".to_string(),
+                near_sdk::serde_json::json!({ "market_id": market_id })
+                    .to_string()
+                    .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_SNAPSHOT,
+            );
         } else {
-            // Disputer won — revert to Closed for re-resolution
-            market.status = MarketStatus::Closed;
+            // Disputer won — revert to Closed for re-resolution, or reopen
+            // trading if the event was wrongly asserted as already decided
+            market.status = if market.early_assertion {
+                MarketStatus::Open
+            } else {
+                MarketStatus::Closed
+            };
+            Event::AssertionRejected {
+                market_id,
+                assertion_id: &assertion_id,
+                rejected_outcome: market.asserted_outcome,
+                restored_status: market.status,
+            }
+            .emit();
+            market.early_assertion = false;
             market.asserted_outcome = None;
             market.assertion_id = None;
             market.resolver = None;
             market.disputer = None;
             market.assertion_submitted_at_ns = None;
             market.assertion_expires_at_ns = None;
+            market.assertion_claim = None;
             self.assertion_to_market.remove(&assertion_bytes);
         }
 
@@ -216,11 +279,6 @@ impl MarketContract {
     }
 
     pub fn assertion_disputed_callback(&mut self, assertion_id: String) {
-        require!(
-            env::predecessor_account_id() == self.oracle,
-            "Only oracle can call this callback"
-        );
-
         let assertion_bytes: Bytes32 = hex::decode(&assertion_id)
             .expect("Invalid assertion_id hex")
             .try_into()
@@ -233,6 +291,11 @@ impl MarketContract {
             .expect("No market found for this assertion");
 
         let market = self.markets.get(&market_id).expect("Market not found");
+        // Only the oracle the market was created with can settle it
+        require!(
+            env::predecessor_account_id() == market.oracle,
+            "Only oracle can call this callback"
+        );
         let mut market = market.clone();
         market.status = MarketStatus::Disputed;
         market.disputer = None;
@@ -245,16 +308,32 @@ impl MarketContract {
         .emit();
     }
 
+    /// Set the minimum bond for early resolution. Applied through an admin action.
+    pub(crate) fn set_early_resolution_bond(&mut self, amount: U128) {
+        self.early_resolution_bond = amount.0;
+
+        Event::EarlyResolutionBondUpdated { amount }.emit();
+    }
+
+    pub(crate) fn claim_hash(claim_data: &str) -> Bytes32 {
+        env::keccak256(claim_data.as_bytes())
+            .try_into()
+            .expect("keccak256 produces 32 bytes")
+    }
+
+    /// Assertion id as the oracle derives it. This contract is always both the
+    /// caller and the callback recipient, and asserts under the default identifier.
     fn compute_assertion_id(
         claim: Bytes32,
         bond: u128,
         time: u64,
         liveness: u64,
         currency: &AccountId,
-        callback_recipient: &Option<AccountId>,
-        identifier: &Bytes32,
-        caller: &AccountId,
     ) -> Bytes32 {
+        let caller = env::current_account_id();
+        let callback_recipient = Some(caller.clone());
+        let identifier = &DEFAULT_IDENTIFIER;
+
         let mut data = Vec::new();
         data.extend_from_slice(&claim);
         data.extend_from_slice(&bond.to_le_bytes());
@@ -276,70 +355,23 @@ impl MarketContract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use near_sdk::store::LookupMap;
-    use near_sdk::test_utils::VMContextBuilder;
-    use near_sdk::testing_env;
-    use near_sdk::AccountId;
-
-    fn account(id: &str) -> AccountId {
-        id.parse().unwrap()
-    }
-
-    fn context(predecessor: &str, current: &str, ts: u64) -> VMContextBuilder {
-        let mut builder = VMContextBuilder::new();
-        builder
-            .predecessor_account_id(account(predecessor))
-            .current_account_id(account(current))
-            .block_timestamp(ts);
-        builder
-    }
-
-    fn base_market(id: u64, resolution_time_ns: u64, creator: &str) -> Market {
-        Market {
-            id,
-            question: "Will test pass?".to_string(),
-            description: "test".to_string(),
-            creator: account(creator),
-            resolution_time_ns,
-            status: MarketStatus::Open,
-            outcome: None,
-            yes_reserve: 50 * USDC_ONE,
-            no_reserve: 50 * USDC_ONE,
-            total_lp_shares: 100 * USDC_ONE,
-            total_collateral: 100 * USDC_ONE,
-            fee_bps: DEFAULT_FEE_BPS,
-            accrued_fees: 0,
-            assertion_id: None,
-            asserted_outcome: None,
-            resolver: None,
-            disputer: None,
-            assertion_submitted_at_ns: None,
-            assertion_expires_at_ns: None,
-        }
-    }
-
-    fn test_contract() -> MarketContract {
-        MarketContract {
-            owner: account("owner.testnet"),
-            usdc_token: account("usdc.testnet"),
-            outcome_token: account("outcome.testnet"),
-            oracle: account("oracle.testnet"),
-            markets: LookupMap::new(b"m"),
-            market_count: 1,
-            lp_positions: LookupMap::new(b"l"),
-            assertion_to_market: LookupMap::new(b"a"),
-        }
+    use crate::test_utils::*;
+
+    fn market_resolving_at(resolution_time_ns: u64) -> MarketContract {
+        ContractBuilder::new()
+            .market_with(CreateMarketArgs {
+                resolution_time_ns: U64(resolution_time_ns),
+                ..market_args()
+            })
+            .build()
     }
 
     #[test]
     fn propose_then_undisputed_settle() {
-        let mut contract = test_contract();
-        contract
-            .markets
-            .insert(0, base_market(0, 100, "creator.testnet"));
+        let mut contract = market_resolving_at(100);
 
         let submit_ts = 120;
-        testing_env!(context("usdc.testnet", "market.testnet", submit_ts).build());
+        set_context_at("usdc.testnet", submit_ts);
         contract.internal_submit_resolution(
             0,
             Outcome::Yes,
@@ -352,7 +384,7 @@ mod tests {
         assert!(resolving.assertion_id.is_some());
         let assertion_id = resolving.assertion_id.unwrap();
 
-        testing_env!(context("oracle.testnet", "market.testnet", 200).build());
+        set_context_at("oracle.testnet", 200);
         contract.assertion_resolved_callback(hex::encode(assertion_id), true);
 
         let settled = contract.markets.get(&0).unwrap();
@@ -363,13 +395,10 @@ mod tests {
 
     #[test]
     fn propose_then_dispute_then_false_settle_reopens_market() {
-        let mut contract = test_contract();
-        contract
-            .markets
-            .insert(0, base_market(0, 100, "creator.testnet"));
+        let mut contract = market_resolving_at(100);
 
         let submit_ts = 130;
-        testing_env!(context("usdc.testnet", "market.testnet", submit_ts).build());
+        set_context_at("usdc.testnet", submit_ts);
         contract.internal_submit_resolution(
             0,
             Outcome::No,
@@ -384,14 +413,14 @@ mod tests {
             .expect("assertion id set");
         let assertion_hex = hex::encode(assertion_id);
 
-        testing_env!(context("oracle.testnet", "market.testnet", 150).build());
+        set_context_at("oracle.testnet", 150);
         contract.assertion_disputed_callback(assertion_hex.clone());
         assert_eq!(
             contract.markets.get(&0).unwrap().status,
             MarketStatus::Disputed
         );
 
-        testing_env!(context("oracle.testnet", "market.testnet", 170).build());
+        set_context_at("oracle.testnet", 170);
         contract.assertion_resolved_callback(assertion_hex, false);
 
         let reopened = contract.markets.get(&0).unwrap();
@@ -400,11 +429,188 @@ mod tests {
         assert!(reopened.asserted_outcome.is_none());
     }
 
+    #[test]
+    fn assertion_claim_is_readable_and_matches_hash() {
+        let mut contract = market_resolving_at(100);
+
+        set_context_at("usdc.testnet", 130);
+        contract.internal_submit_resolution(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            account("resolver.testnet"),
+        );
+
+        let status = contract.get_resolution_status(0);
+        let claim_data = status.claim_data.expect("claim stored");
+        let claim: ResolutionClaim = near_sdk::serde_json::from_str(&claim_data).unwrap();
+        assert_eq!(claim.market_contract, account("market.testnet"));
+        assert_eq!(claim.question, "Will test pass?");
+        assert_eq!(claim.resolution_criteria, "test");
+        assert_eq!(claim.asserted_outcome, Outcome::Yes);
+        assert_eq!(
+            status.claim_hash.unwrap(),
+            hex::encode(env::keccak256(claim_data.as_bytes()))
+        );
+
+        // A rejected assertion drops its claim
+        let assertion_hex = status.active_assertion_id.unwrap();
+        set_context_at("oracle.testnet", 170);
+        contract.assertion_resolved_callback(assertion_hex, false);
+        assert!(contract.get_resolution_status(0).claim_data.is_none());
+    }
+
+    fn priority_market() -> MarketContract {
+        ContractBuilder::new()
+            .market_with(CreateMarketArgs {
+                resolution_time_ns: U64(100),
+                resolution_priority: Some(ResolutionPriority {
+                    resolver: Some(account("reporter.testnet")),
+                    window_ns: U64(50),
+                }),
+                ..market_args()
+            })
+            .build()
+    }
+
+    #[test]
+    fn designated_resolver_can_resolve_during_priority_window() {
+        let mut contract = priority_market();
+
+        set_context_at("usdc.testnet", 120);
+        let status = contract.get_resolution_status(0);
+        assert_eq!(status.priority_window_ends_at_ns, Some(U64(150)));
+        assert_eq!(
+            status.priority_resolvers,
+            vec![account("creator.testnet"), account("reporter.testnet")]
+        );
+
+        contract.internal_submit_resolution(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            account("reporter.testnet"),
+        );
+        assert_eq!(
+            contract.markets.get(&0).unwrap().status,
+            MarketStatus::Resolving
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Only the creator or designated resolver can resolve during the priority window"
+    )]
+    fn others_wait_for_priority_window() {
+        let mut contract = priority_market();
+
+        set_context_at("usdc.testnet", 149);
+        contract.internal_submit_resolution(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            account("resolver.testnet"),
+        );
+    }
+
+    #[test]
+    fn anyone_resolves_after_priority_window() {
+        let mut contract = priority_market();
+
+        set_context_at("usdc.testnet", 150);
+        contract.internal_submit_resolution(
+            0,
+            Outcome::No,
+            10 * USDC_ONE,
+            account("resolver.testnet"),
+        );
+        assert_eq!(
+            contract.markets.get(&0).unwrap().resolver,
+            Some(account("resolver.testnet"))
+        );
+    }
+
+    #[test]
+    fn early_resolution_halts_trading_and_reopens_when_rejected() {
+        let mut contract = market_resolving_at(1_000);
+
+        set_context_at("usdc.testnet", 120);
+        contract.internal_submit_resolution(
+            0,
+            Outcome::Yes,
+            DEFAULT_EARLY_RESOLUTION_BOND,
+            account("resolver.testnet"),
+        );
+        let status = contract.get_resolution_status(0);
+        assert_eq!(status.status, MarketStatus::Resolving);
+        assert!(status.is_early_assertion);
+        assert_eq!(
+            status.assertion_expires_at_ns,
+            Some(U64(120 + EARLY_ORACLE_LIVENESS_NS))
+        );
+
+        set_context_at("oracle.testnet", 170);
+        contract.assertion_resolved_callback(status.active_assertion_id.unwrap(), false);
+
+        let reopened = contract.markets.get(&0).unwrap();
+        assert_eq!(reopened.status, MarketStatus::Open);
+        assert!(!reopened.early_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "Early resolution requires a bond of at least")]
+    fn early_resolution_requires_larger_bond() {
+        let mut contract = market_resolving_at(1_000);
+
+        set_context_at("usdc.testnet", 120);
+        contract.internal_submit_resolution(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            account("resolver.testnet"),
+        );
+    }
+
+    #[test]
+    fn rejected_assertion_emits_event() {
+        let mut contract = market_resolving_at(100);
+
+        set_context_at("usdc.testnet", 130);
+        contract.internal_submit_resolution(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            account("resolver.testnet"),
+        );
+        let assertion_hex = hex::encode(contract.markets.get(&0).unwrap().assertion_id.unwrap());
+
+        set_context_at("oracle.testnet", 170);
+        contract.assertion_resolved_callback(assertion_hex.clone(), false);
+
+        let logs = near_sdk::test_utils::get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("\"event\":\"assertion_rejected\""));
+        assert!(logs[0].contains(&assertion_hex));
+        assert!(logs[0].contains("\"rejected_outcome\":\"Yes\""));
+    }
+
     #[test]
     #[should_panic(expected = "Only oracle can call this callback")]
     fn callback_rejected_for_non_oracle_caller() {
-        let mut contract = test_contract();
-        testing_env!(context("attacker.testnet", "market.testnet", 999).build());
+        let mut contract = market_resolving_at(100);
+        contract.assertion_to_market.insert([0u8; 32], 0);
+        set_context_at("attacker.testnet", 999);
+        contract.assertion_disputed_callback("00".repeat(32));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only oracle can call this callback")]
+    fn callback_rejected_from_replaced_oracle() {
+        let mut contract = market_resolving_at(100);
+        contract.assertion_to_market.insert([0u8; 32], 0);
+        // The global oracle changed after the market was created
+        contract.oracle = account("new-oracle.testnet");
+        set_context_at("new-oracle.testnet", 999);
         contract.assertion_disputed_callback("00".repeat(32));
     }
 }
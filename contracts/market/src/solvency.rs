@@ -0,0 +1,299 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require, AccountId, Gas, NearToken, Promise, PromiseResult};
+
+use market_types::*;
+
+use crate::events::Event;
+use crate::{MarketContract, MarketContractExt};
+
+const GAS_FOR_FT_BALANCE_OF: Gas = Gas::from_tgas(5);
+const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_BALANCE_CALLBACK: Gas = Gas::from_tgas(20);
+
+#[near]
+impl MarketContract {
+    // ── Solvency Views ─────────────────────────────────────────────────
+
+    /// Amount of `token` the contract owes: open-market collateral, LP and creator
+    /// fees, held creation bonds and the treasury balance. Anything held above
+    /// this is surplus.
+    pub fn get_total_owed(&self, token: AccountId) -> U128 {
+        U128(self.owed(&token))
+    }
+
+    /// Cross-check a market's reserves, LP shares, collateral and outcome supply.
+    pub fn check_invariants(&self, market_id: MarketId) -> InvariantReport {
+        let market = self.markets.get(&market_id).expect("Market not found");
+        let total_owed = self.owed(&market.collateral_token);
+
+        let lp_shares_match_collateral =
+            (market.total_lp_shares == 0) == (market.total_collateral == 0);
+        let reserves_non_zero = market.status != MarketStatus::Open
+            || market.total_lp_shares == 0
+            || (market.yes_reserve > 0 && market.no_reserve > 0);
+        let collateral_covers_supply =
+            market.total_collateral >= market.yes_supply.max(market.no_supply);
+        let held_bond = if market.bond_status == BondStatus::Held {
+            market.creation_bond
+        } else {
+            0
+        };
+        let owed_covers_market = market
+            .total_collateral
+            .checked_add(market.accrued_fees)
+            .and_then(|owed| owed.checked_add(market.creator_fees))
+            .and_then(|owed| owed.checked_add(held_bond))
+            .is_some_and(|market_owed| total_owed >= market_owed);
+
+        InvariantReport {
+            market_id: U64(market_id),
+            yes_reserve: U128(market.yes_reserve),
+            no_reserve: U128(market.no_reserve),
+            total_lp_shares: U128(market.total_lp_shares),
+            total_collateral: U128(market.total_collateral),
+            accrued_fees: U128(market.accrued_fees),
+            yes_supply: U128(market.yes_supply),
+            no_supply: U128(market.no_supply),
+            total_owed: U128(total_owed),
+            lp_shares_match_collateral,
+            reserves_non_zero,
+            collateral_covers_supply,
+            owed_covers_market,
+            ok: lp_shares_match_collateral
+                && reserves_non_zero
+                && collateral_covers_supply
+                && owed_covers_market,
+        }
+    }
+
+    // ── Surplus Withdrawal ─────────────────────────────────────────────
+
+    /// Execute a queued surplus withdrawal once the balance check passes. The
+    /// action is only removed and reported executed here; a failed check
+    /// leaves it pending.
+    #[private]
+    pub fn on_emergency_balance(&mut self, action_id: U64) -> Promise {
+        let pending = self
+            .pending_actions
+            .get(&action_id.0)
+            .expect("Admin action not found");
+        let AdminAction::EmergencyWithdrawToken {
+            token,
+            receiver_id,
+            amount,
+            ..
+        } = pending.action.clone()
+        else {
+            env::panic_str("Not a token withdrawal");
+        };
+        let balance = match env::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                near_sdk::serde_json::from_slice::<U128>(&bytes)
+                    .expect("Invalid ft_balance_of response")
+                    .0
+            }
+            _ => env::panic_str("Failed to fetch token balance"),
+        };
+        let surplus = balance.saturating_sub(self.owed(&token));
+        require!(
+            amount.0 <= surplus,
+            format!("Amount exceeds surplus of {surplus}")
+        );
+        let pending = self
+            .pending_actions
+            .remove(&action_id.0)
+            .expect("Admin action not found");
+        Event::AdminActionExecuted {
+            action_id,
+            action: &pending.action,
+        }
+        .emit();
+        self.internal_emergency_transfer(token, receiver_id, amount, false)
+    }
+}
+
+impl MarketContract {
+    fn owed(&self, token: &AccountId) -> u128 {
+        self.total_owed.get(token).copied().unwrap_or(0)
+    }
+
+    /// Record `amount` of `token` received on behalf of LPs, traders or the treasury.
+    pub(crate) fn credit_owed(&mut self, token: &AccountId, amount: u128) {
+        let owed = self
+            .owed(token)
+            .checked_add(amount)
+            .unwrap_or_else(|| env::panic_str("Math overflow"));
+        self.total_owed.insert(token.clone(), owed);
+    }
+
+    /// Record `amount` of `token` paid out to LPs, traders or the treasury.
+    /// Saturates so bookkeeping drift never blocks a payout; `check_invariants`
+    /// reports it instead.
+    pub(crate) fn debit_owed(&mut self, token: &AccountId, amount: u128) {
+        let owed = self.owed(token).saturating_sub(amount);
+        self.total_owed.insert(token.clone(), owed);
+    }
+
+    /// Ask the token for the contract's balance, then let queued action
+    /// `action_id` withdraw only the surplus.
+    pub(crate) fn internal_emergency_surplus_withdraw(&self, action_id: U64) -> Promise {
+        let Some(PendingAction {
+            action: AdminAction::EmergencyWithdrawToken { token, .. },
+            ..
+        }) = self.pending_actions.get(&action_id.0)
+        else {
+            env::panic_str("Not a token withdrawal");
+        };
+        Promise::new(token.clone())
+            .function_call(
+                "ft_balance_of".to_string(),
+                near_sdk::serde_json::json!({
+                    "account_id": env::current_account_id(),
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_FT_BALANCE_OF,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_emergency_balance".to_string(),
+                    near_sdk::serde_json::json!({ "action_id": action_id })
+                        .to_string()
+                        .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_BALANCE_CALLBACK,
+                ),
+            )
+    }
+
+    pub(crate) fn internal_emergency_transfer(
+        &self,
+        token: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        forced: bool,
+    ) -> Promise {
+        Event::EmergencyTokenWithdrawal {
+            token: &token,
+            receiver_id: &receiver_id,
+            amount,
+            forced,
+        }
+        .emit();
+
+        Promise::new(token).function_call(
+            "ft_transfer".to_string(),
+            near_sdk::serde_json::json!({
+                "receiver_id": receiver_id,
+                "amount": amount,
+            })
+            .to_string()
+            .into_bytes(),
+            NearToken::from_yoctonear(1),
+            GAS_FOR_FT_TRANSFER,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use near_sdk::test_utils::get_logs;
+
+    fn queue_withdrawal(contract: &mut MarketContract, amount: u128) -> U64 {
+        set_context("owner.testnet", vec![]);
+        contract.propose_action(AdminAction::EmergencyWithdrawToken {
+            token: account("usdc.testnet"),
+            receiver_id: account("owner.testnet"),
+            amount: U128(amount),
+            force: false,
+        })
+    }
+
+    fn owed(contract: &MarketContract) -> u128 {
+        contract.get_total_owed(account("usdc.testnet")).0
+    }
+
+    #[test]
+    fn owed_tracks_deposits_and_payouts() {
+        let mut contract = ContractBuilder::new().market().build();
+        assert_eq!(owed(&contract), 100 * USDC_ONE);
+
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            10 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+        assert_eq!(owed(&contract), 110 * USDC_ONE);
+
+        set_context("buyer.testnet", vec![]);
+        contract.sell(0, Outcome::Yes, U128(5 * USDC_ONE), U128(0));
+        let market = contract.markets.get(&0).unwrap();
+        assert_eq!(
+            owed(&contract),
+            market.total_collateral
+                + market.accrued_fees
+                + contract.protocol_fee_balance(&account("usdc.testnet"))
+        );
+
+        let report = contract.check_invariants(0);
+        assert!(report.ok, "{report:?}");
+        assert_eq!(report.yes_supply, U128(market.yes_supply));
+    }
+
+    #[test]
+    fn market_ids_start_at_first_market_id() {
+        let contract = ContractBuilder::new()
+            .first_market_id(1_000_000)
+            .market()
+            .build();
+        assert!(contract.markets.get(&1_000_000).is_some());
+        assert_eq!(contract.get_market_count(), 1_000_001);
+    }
+
+    #[test]
+    fn invariants_flag_under_collateralized_market() {
+        let mut contract = ContractBuilder::new().market().build();
+        let mut market = contract.markets.get(&0).unwrap().clone();
+        market.yes_supply = 150 * USDC_ONE;
+        contract.markets.insert(0, market);
+
+        let report = contract.check_invariants(0);
+        assert!(!report.collateral_covers_supply);
+        assert!(!report.ok);
+    }
+
+    #[test]
+    fn surplus_withdrawal_within_surplus_transfers() {
+        let mut contract = ContractBuilder::new().market().build();
+        let action_id = queue_withdrawal(&mut contract, 3 * USDC_ONE);
+        let balance = near_sdk::serde_json::to_vec(&U128(103 * USDC_ONE)).unwrap();
+        set_context("market.testnet", vec![PromiseResult::Successful(balance)]);
+
+        contract.on_emergency_balance(action_id);
+        let logs = get_logs();
+        assert!(logs
+            .iter()
+            .any(|log| log.contains("\"event\":\"admin_action_executed\"")));
+        assert!(logs
+            .iter()
+            .any(|log| log.contains("\"event\":\"emergency_token_withdrawal\"")));
+        assert!(contract.get_pending_action(action_id).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Amount exceeds surplus of 3000000")]
+    fn surplus_withdrawal_cannot_touch_owed_funds() {
+        let mut contract = ContractBuilder::new().market().build();
+        let action_id = queue_withdrawal(&mut contract, 4 * USDC_ONE);
+        let balance = near_sdk::serde_json::to_vec(&U128(103 * USDC_ONE)).unwrap();
+        set_context("market.testnet", vec![PromiseResult::Successful(balance)]);
+
+        contract.on_emergency_balance(action_id);
+    }
+}
@@ -0,0 +1,217 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{env, near, require};
+
+use market_types::*;
+
+use crate::{MarketContract, MarketContractExt};
+
+#[near]
+impl MarketContract {
+    /// Time-weighted average YES/NO price over the last `window_ns`. If the
+    /// stored history is shorter, averages over all of it; `window_start_ns`
+    /// reports the window actually used.
+    pub fn get_twap(&self, market_id: MarketId, window_ns: U64) -> TwapView {
+        let window_ns = window_ns.0;
+        require!(window_ns > 0, "Window must be positive");
+        let market = self.markets.get(&market_id).expect("Market not found");
+        let observations = self
+            .price_observations
+            .get(&market_id)
+            .expect("No price history for market")
+            .chronological();
+
+        let now = env::block_timestamp();
+        let current = Self::observation_at(&observations, market, now);
+        let window_start = now
+            .saturating_sub(window_ns)
+            .max(observations[0].timestamp_ns);
+        if window_start == now {
+            let (yes_price, no_price) = market.prices();
+            return TwapView {
+                market_id: U64(market_id),
+                window_start_ns: U64(now),
+                window_end_ns: U64(now),
+                yes_price: U128(yes_price),
+                no_price: U128(no_price),
+            };
+        }
+
+        // Cumulative value at window start, interpolated between the surrounding points
+        let after = observations
+            .iter()
+            .position(|o| o.timestamp_ns > window_start)
+            .unwrap_or(observations.len());
+        let before = observations[after - 1];
+        let next = observations.get(after).copied().unwrap_or(current);
+        let start_cumulative = if next.timestamp_ns == before.timestamp_ns {
+            before.yes_price_cumulative
+        } else {
+            before.yes_price_cumulative
+                + (next.yes_price_cumulative - before.yes_price_cumulative)
+                    * (window_start - before.timestamp_ns) as u128
+                    / (next.timestamp_ns - before.timestamp_ns) as u128
+        };
+
+        let yes_price =
+            (current.yes_price_cumulative - start_cumulative) / (now - window_start) as u128;
+        TwapView {
+            market_id: U64(market_id),
+            window_start_ns: U64(window_start),
+            window_end_ns: U64(now),
+            yes_price: U128(yes_price),
+            no_price: U128(AMM_SCALE - yes_price),
+        }
+    }
+
+    // ── Accumulator ────────────────────────────────────────────────────
+    // Must run before a trade changes reserves, so the elapsed time is
+    // weighted by the price that was in effect during it.
+
+    pub(crate) fn record_price_observation(&mut self, market: &Market) {
+        let now = env::block_timestamp();
+        let mut history = self
+            .price_observations
+            .get(&market.id)
+            .cloned()
+            .unwrap_or_default();
+        let observations = history.chronological();
+        if history
+            .latest()
+            .is_some_and(|last| last.timestamp_ns == now)
+        {
+            // Already observed in this block; later price changes apply from `now` on
+            return;
+        }
+
+        history.record(Self::observation_at(&observations, market, now));
+        self.price_observations.insert(market.id, history);
+    }
+
+    /// Extend the latest observation to `now` at the market's current spot price.
+    fn observation_at(
+        observations: &[PriceObservation],
+        market: &Market,
+        now: u64,
+    ) -> PriceObservation {
+        let Some(last) = observations.last() else {
+            return PriceObservation {
+                timestamp_ns: now,
+                yes_price_cumulative: 0,
+            };
+        };
+        let (yes_price, _) = market.prices();
+        PriceObservation {
+            timestamp_ns: now,
+            yes_price_cumulative: last.yes_price_cumulative
+                + yes_price * now.saturating_sub(last.timestamp_ns) as u128,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn at(ts: u64) {
+        set_context_at("usdc.testnet", ts);
+    }
+
+    /// Market created at `ts`, its first price observation.
+    fn created_at(ts: u64) -> MarketContract {
+        let mut contract = ContractBuilder::new().build();
+        at(ts);
+        create_market(&mut contract, market_args(), 100 * USDC_ONE);
+        contract
+    }
+
+    #[test]
+    fn twap_weights_prices_by_time() {
+        let mut contract = created_at(1_000);
+
+        // 50/50 for 1000ns, then a YES buy moves the price
+        at(2_000);
+        contract.internal_buy(
+            0,
+            Outcome::Yes,
+            50 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+        let (moved_yes, _) = contract.markets.get(&0).unwrap().prices();
+        assert!(moved_yes > AMM_SCALE / 2);
+
+        at(3_000);
+        let twap = contract.get_twap(0, U64(2_000));
+        assert_eq!(twap.yes_price.0, (AMM_SCALE / 2 + moved_yes) / 2);
+        assert_eq!(twap.window_start_ns, U64(1_000));
+
+        // Window entirely after the trade sees only the new price
+        let recent = contract.get_twap(0, U64(500));
+        assert_eq!(recent.yes_price.0, moved_yes);
+        assert_eq!(recent.no_price.0, AMM_SCALE - moved_yes);
+    }
+
+    #[test]
+    fn twap_interpolates_between_observations() {
+        let mut contract = created_at(1_000);
+        at(2_000);
+        contract.internal_buy(
+            0,
+            Outcome::No,
+            30 * USDC_ONE,
+            0,
+            account("buyer.testnet"),
+            None,
+        );
+        let (moved_yes, _) = contract.markets.get(&0).unwrap().prices();
+
+        // Window starts halfway through the 50/50 period
+        at(2_500);
+        let twap = contract.get_twap(0, U64(1_000));
+        assert_eq!(twap.yes_price.0, (AMM_SCALE / 2 + moved_yes) / 2);
+    }
+
+    #[test]
+    fn ring_buffer_keeps_latest_observations() {
+        let mut contract = created_at(1);
+        for i in 0..(TWAP_OBSERVATION_CAPACITY as u64 + 10) {
+            at(10 + i * TWAP_MIN_OBSERVATION_INTERVAL_NS);
+            contract.internal_buy(0, Outcome::Yes, USDC_ONE, 0, account("buyer.testnet"), None);
+        }
+
+        let history = contract.price_observations.get(&0).unwrap();
+        assert_eq!(history.observations.len(), TWAP_OBSERVATION_CAPACITY);
+        let ordered = history.chronological();
+        assert!(ordered
+            .windows(2)
+            .all(|w| w[0].timestamp_ns < w[1].timestamp_ns));
+        assert_eq!(
+            history.latest().unwrap().timestamp_ns,
+            ordered.last().unwrap().timestamp_ns
+        );
+    }
+
+    #[test]
+    fn rapid_trades_do_not_evict_history() {
+        let mut contract = created_at(1_000);
+        for i in 1..=(TWAP_OBSERVATION_CAPACITY as u64 * 2) {
+            at(1_000 + i);
+            contract.internal_buy(0, Outcome::Yes, USDC_ONE, 0, account("buyer.testnet"), None);
+        }
+
+        let history = contract.price_observations.get(&0).unwrap();
+        assert_eq!(history.observations.len(), 2);
+        assert_eq!(history.chronological()[0].timestamp_ns, 1_000);
+    }
+
+    #[test]
+    fn twap_clamps_window_to_available_history() {
+        let contract = created_at(1_000);
+        at(2_000);
+        let twap = contract.get_twap(0, U64(5_000));
+        assert_eq!(twap.window_start_ns, U64(1_000));
+        assert_eq!(twap.yes_price.0, AMM_SCALE / 2);
+    }
+}
@@ -1,8 +1,9 @@
 use near_sdk::json_types::{U128, U64};
-use near_sdk::near;
+use near_sdk::{near, AccountId};
 
 use market_types::*;
 
+use crate::amm::checked;
 use crate::{MarketContract, MarketContractExt};
 
 #[near]
@@ -17,41 +18,27 @@ impl MarketContract {
 
     pub fn get_prices(&self, market_id: MarketId) -> (U128, U128) {
         let market = self.markets.get(&market_id).expect("Market not found");
-        let total = market.yes_reserve + market.no_reserve;
-        if total == 0 {
-            return (U128(AMM_SCALE / 2), U128(AMM_SCALE / 2));
-        }
-        (
-            U128(market.no_reserve * AMM_SCALE / total),
-            U128(market.yes_reserve * AMM_SCALE / total),
-        )
+        let (yes_price, no_price) = market.prices();
+        (U128(yes_price), U128(no_price))
     }
 
     pub fn estimate_buy(&self, market_id: MarketId, outcome: Outcome, collateral_in: U128) -> U128 {
         let market = self.markets.get(&market_id).expect("Market not found");
-        let collateral_in = collateral_in.0;
-
-        let fee = collateral_in * market.fee_bps as u128 / BPS_DENOMINATOR as u128;
-        let net = collateral_in - fee;
-
-        let yes_r = market.yes_reserve + net;
-        let no_r = market.no_reserve + net;
-        let k = yes_r * no_r;
-
-        let tokens_out = match outcome {
-            Outcome::Yes => {
-                let final_no = no_r + net;
-                let final_yes = k / final_no + 1;
-                yes_r - final_yes
-            }
-            Outcome::No => {
-                let final_yes = yes_r + net;
-                let final_no = k / final_yes + 1;
-                no_r - final_no
-            }
-        };
+        let fee_bps = market.fee_bps_at(near_sdk::env::block_timestamp());
+        let quote = checked(market_math::quote_buy(
+            market.yes_reserve,
+            market.no_reserve,
+            collateral_in.0,
+            fee_bps,
+            outcome.into(),
+        ));
+        U128(quote.tokens_out)
+    }
 
-        U128(tokens_out)
+    /// Trading fee currently charged by a market, including any dynamic ramp.
+    pub fn get_current_fee_bps(&self, market_id: MarketId) -> u16 {
+        let market = self.markets.get(&market_id).expect("Market not found");
+        market.fee_bps_at(near_sdk::env::block_timestamp())
     }
 
     pub fn get_lp_shares(&self, market_id: MarketId, account_id: near_sdk::AccountId) -> U128 {
@@ -66,7 +53,36 @@ impl MarketContract {
             outcome_token: self.outcome_token.clone(),
             oracle: self.oracle.clone(),
             market_count: U64(self.market_count),
-            default_fee_bps: DEFAULT_FEE_BPS,
+            default_fee_bps: self.default_fee_bps,
+            fee_tiers: self.fee_tiers.clone(),
+            protocol_fee_share_bps: self.protocol_fee_share_bps,
+            treasurer: self.treasurer.clone(),
+            guardian: self.guardian.clone(),
+            timelock_delay_ns: U64(self.timelock_delay_ns),
+            creation_bond: U128(self.creation_bond),
+            creator_fee_share_bps: self.creator_fee_share_bps,
+            curators: self.curators.clone(),
+            creation_mode: self.creation_mode,
+            early_resolution_bond: U128(self.early_resolution_bond),
+        }
+    }
+
+    /// Lifetime protocol fees collected by a single market.
+    pub fn get_protocol_fees(&self, market_id: MarketId) -> U128 {
+        let market = self.markets.get(&market_id).expect("Market not found");
+        U128(market.protocol_fees)
+    }
+
+    /// Protocol revenue in `token`, defaulting to the current collateral token.
+    pub fn get_protocol_revenue(&self, token: Option<AccountId>) -> ProtocolRevenueView {
+        let token = token.unwrap_or_else(|| self.usdc_token.clone());
+        let balance = self.protocol_fee_balance(&token);
+        let total_accrued = self.protocol_fees_accrued(&token);
+        ProtocolRevenueView {
+            token,
+            balance: U128(balance),
+            total_accrued: U128(total_accrued),
+            total_withdrawn: U128(total_accrued - balance),
         }
     }
 
@@ -80,6 +96,10 @@ impl MarketContract {
                 .assertion_expires_at_ns
                 .map(|expiry| now < expiry)
                 .unwrap_or(false);
+        let claim_data = market
+            .assertion_claim
+            .as_ref()
+            .map(ResolutionClaim::preimage);
 
         ResolutionStatusView {
             market_id: U64(market_id),
@@ -90,6 +110,11 @@ impl MarketContract {
             disputer: market.disputer.clone(),
             assertion_submitted_at_ns: market.assertion_submitted_at_ns.map(U64),
             assertion_expires_at_ns: market.assertion_expires_at_ns.map(U64),
+            is_early_assertion: market.early_assertion,
+            priority_window_ends_at_ns: market.priority_window_end_ns().map(U64),
+            priority_resolvers: market.priority_resolvers(),
+            claim_data: claim_data.clone(),
+            claim_hash: claim_data.map(|data| hex::encode(Self::claim_hash(&data))),
             now_ns: U64(now),
             is_disputable_now,
             is_resolvable_now,
@@ -0,0 +1,500 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{assert_one_yocto, env, near, require, AccountId};
+
+use crate::events::NestEvent;
+use crate::storage::{refund, storage_cost};
+use crate::{OutcomeToken, OutcomeTokenExt};
+
+/// Amount of a single token a spender may move on the owner's behalf.
+#[near(serializers = [borsh])]
+#[derive(Clone, Debug)]
+pub struct Allowance {
+    pub amount: u128,
+    pub approval_id: u64,
+    pub expires_at_ns: Option<u64>,
+}
+
+/// Operator allowed to move any amount of every token the owner holds.
+#[near(serializers = [borsh])]
+#[derive(Clone, Debug)]
+pub struct OperatorApproval {
+    pub approval_id: u64,
+    pub expires_at_ns: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AllowanceView {
+    pub owner_id: AccountId,
+    pub spender_id: AccountId,
+    pub token_id: String,
+    pub amount: U128,
+    pub approval_id: u64,
+    pub expires_at_ns: Option<U64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OperatorView {
+    pub owner_id: AccountId,
+    pub operator_id: AccountId,
+    pub approval_id: u64,
+    pub expires_at_ns: Option<U64>,
+}
+
+/// Key for an allowance: owner, token id and spender separated by NUL
+/// (which cannot appear in account ids or token ids).
+fn allowance_key(owner_id: &AccountId, token_id: &str, spender_id: &AccountId) -> Vec<u8> {
+    [owner_id.as_bytes(), token_id.as_bytes(), spender_id.as_bytes()].join(&0u8)
+}
+
+fn operator_key(owner_id: &AccountId, operator_id: &AccountId) -> Vec<u8> {
+    [owner_id.as_bytes(), operator_id.as_bytes()].join(&0u8)
+}
+
+fn is_expired(expires_at_ns: Option<u64>) -> bool {
+    expires_at_ns.is_some_and(|expiry| env::block_timestamp() >= expiry)
+}
+
+fn validate_expiry(expires_at_ns: Option<U64>) -> Option<u64> {
+    let expires_at_ns = expires_at_ns.map(|e| e.0);
+    require!(
+        !is_expired(expires_at_ns),
+        "Expiry must be in the future"
+    );
+    expires_at_ns
+}
+
+#[near]
+impl OutcomeToken {
+    // ── Allowances ─────────────────────────────────────────────────────
+
+    /// Allow `spender_id` to transfer up to `amount` of `token_id` from the caller,
+    /// replacing any existing allowance. The attached deposit pays for the new
+    /// entry and the excess is refunded. Returns the approval id to pass in transfers.
+    #[payable]
+    pub fn mt_approve(
+        &mut self,
+        token_id: String,
+        spender_id: AccountId,
+        amount: U128,
+        expires_at_ns: Option<U64>,
+    ) -> u64 {
+        let owner_id = env::predecessor_account_id();
+        require!(owner_id != spender_id, "Cannot approve yourself");
+        crate::mt::parse_token_id(&token_id);
+        require!(amount.0 > 0, "Amount must be positive");
+        let expires_at_ns = validate_expiry(expires_at_ns);
+
+        let initial_usage = self.approval_storage_usage();
+        let approval_id = self.next_approval_id();
+        self.allowances.insert(
+            allowance_key(&owner_id, &token_id, &spender_id),
+            Allowance {
+                amount: amount.0,
+                approval_id,
+                expires_at_ns,
+            },
+        );
+        self.charge_approval_storage(&owner_id, initial_usage);
+
+        NestEvent::AllowanceApproved {
+            owner_id: &owner_id,
+            spender_id: &spender_id,
+            token_id: &token_id,
+            amount,
+            approval_id,
+            expires_at_ns: expires_at_ns.map(U64),
+        }
+        .emit();
+        approval_id
+    }
+
+    /// Remove an allowance and refund the storage it used.
+    #[payable]
+    pub fn mt_revoke(&mut self, token_id: String, spender_id: AccountId) {
+        assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+        let initial_usage = self.approval_storage_usage();
+        let removed = self
+            .allowances
+            .remove(&allowance_key(&owner_id, &token_id, &spender_id));
+        require!(removed.is_some(), "No allowance to revoke");
+        self.release_approval_storage(&owner_id, initial_usage);
+
+        NestEvent::AllowanceRevoked {
+            owner_id: &owner_id,
+            spender_id: &spender_id,
+            token_id: &token_id,
+        }
+        .emit();
+    }
+
+    // ── Operators ──────────────────────────────────────────────────────
+
+    /// Allow `operator_id` to transfer any of the caller's outcome tokens. The
+    /// attached deposit pays for the new entry and the excess is refunded.
+    /// Returns the approval id to pass in transfers.
+    #[payable]
+    pub fn mt_set_operator(&mut self, operator_id: AccountId, expires_at_ns: Option<U64>) -> u64 {
+        let owner_id = env::predecessor_account_id();
+        require!(owner_id != operator_id, "Cannot approve yourself");
+        let expires_at_ns = validate_expiry(expires_at_ns);
+
+        let initial_usage = self.approval_storage_usage();
+        let approval_id = self.next_approval_id();
+        self.operators.insert(
+            operator_key(&owner_id, &operator_id),
+            OperatorApproval {
+                approval_id,
+                expires_at_ns,
+            },
+        );
+        self.charge_approval_storage(&owner_id, initial_usage);
+
+        NestEvent::OperatorApproved {
+            owner_id: &owner_id,
+            operator_id: &operator_id,
+            approval_id,
+            expires_at_ns: expires_at_ns.map(U64),
+        }
+        .emit();
+        approval_id
+    }
+
+    /// Remove an operator approval and refund the storage it used.
+    #[payable]
+    pub fn mt_revoke_operator(&mut self, operator_id: AccountId) {
+        assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+        let initial_usage = self.approval_storage_usage();
+        let removed = self
+            .operators
+            .remove(&operator_key(&owner_id, &operator_id));
+        require!(removed.is_some(), "No operator to revoke");
+        self.release_approval_storage(&owner_id, initial_usage);
+
+        NestEvent::OperatorRevoked {
+            owner_id: &owner_id,
+            operator_id: &operator_id,
+        }
+        .emit();
+    }
+
+    // ── Views ──────────────────────────────────────────────────────────
+
+    /// Active allowance, or `None` if missing or expired.
+    pub fn mt_allowance(
+        &self,
+        owner_id: AccountId,
+        token_id: String,
+        spender_id: AccountId,
+    ) -> Option<AllowanceView> {
+        let allowance = self
+            .allowances
+            .get(&allowance_key(&owner_id, &token_id, &spender_id))
+            .filter(|a| !is_expired(a.expires_at_ns))?;
+        Some(AllowanceView {
+            amount: U128(allowance.amount),
+            approval_id: allowance.approval_id,
+            expires_at_ns: allowance.expires_at_ns.map(U64),
+            owner_id,
+            spender_id,
+            token_id,
+        })
+    }
+
+    /// Active operator approval, or `None` if missing or expired.
+    pub fn mt_operator(&self, owner_id: AccountId, operator_id: AccountId) -> Option<OperatorView> {
+        let operator = self
+            .operators
+            .get(&operator_key(&owner_id, &operator_id))
+            .filter(|o| !is_expired(o.expires_at_ns))?;
+        Some(OperatorView {
+            approval_id: operator.approval_id,
+            expires_at_ns: operator.expires_at_ns.map(U64),
+            owner_id,
+            operator_id,
+        })
+    }
+
+    /// NEP-245 approval check: whether `approved_account_id` may move `amounts`
+    /// of `token_ids` from `owner_id`, optionally matching `approval_ids`.
+    pub fn mt_is_approved(
+        &self,
+        owner_id: AccountId,
+        token_ids: Vec<String>,
+        approved_account_id: AccountId,
+        amounts: Vec<U128>,
+        approval_ids: Option<Vec<u64>>,
+    ) -> bool {
+        require!(
+            token_ids.len() == amounts.len(),
+            "Token ids and amounts must have the same length"
+        );
+        if let Some(ids) = &approval_ids {
+            require!(
+                ids.len() == token_ids.len(),
+                "Token ids and approval ids must have the same length"
+            );
+        }
+
+        token_ids.iter().zip(&amounts).enumerate().all(|(i, (token_id, amount))| {
+            let approval_id = approval_ids.as_ref().map(|ids| ids[i]);
+            self.approved_amount(&owner_id, token_id, &approved_account_id, approval_id)
+                .is_some_and(|approved| approved >= amount.0)
+        })
+    }
+}
+
+impl OutcomeToken {
+    fn next_approval_id(&mut self) -> u64 {
+        let id = self.next_approval_id;
+        self.next_approval_id += 1;
+        id
+    }
+
+    /// Storage in use, with cached allowance and operator writes flushed first.
+    fn approval_storage_usage(&mut self) -> u64 {
+        self.allowances.flush();
+        self.operators.flush();
+        env::storage_usage()
+    }
+
+    /// Pay for the storage added since `initial_usage` from the attached deposit
+    /// (at least 1 yoctoNEAR) and refund the excess to `owner_id`.
+    fn charge_approval_storage(&mut self, owner_id: &AccountId, initial_usage: u64) {
+        let cost = storage_cost(self.approval_storage_usage().saturating_sub(initial_usage));
+        let attached = env::attached_deposit().as_yoctonear();
+        require!(
+            attached >= cost.max(1),
+            format!("Must attach {} yoctoNEAR to cover storage", cost.max(1))
+        );
+        refund(owner_id, attached - cost);
+    }
+
+    /// Refund the storage freed since `initial_usage` to `owner_id`.
+    fn release_approval_storage(&mut self, owner_id: &AccountId, initial_usage: u64) {
+        let freed = initial_usage.saturating_sub(self.approval_storage_usage());
+        refund(owner_id, storage_cost(freed));
+    }
+
+    /// Amount `spender_id` may move, or `None` when not approved. Operators are unlimited.
+    fn approved_amount(
+        &self,
+        owner_id: &AccountId,
+        token_id: &str,
+        spender_id: &AccountId,
+        approval_id: Option<u64>,
+    ) -> Option<u128> {
+        let matches = |id: u64| approval_id.is_none_or(|expected| expected == id);
+
+        if let Some(operator) = self.operators.get(&operator_key(owner_id, spender_id)) {
+            if !is_expired(operator.expires_at_ns) && matches(operator.approval_id) {
+                return Some(u128::MAX);
+            }
+        }
+        self.allowances
+            .get(&allowance_key(owner_id, token_id, spender_id))
+            .filter(|a| !is_expired(a.expires_at_ns) && matches(a.approval_id))
+            .map(|a| a.amount)
+    }
+
+    /// Check that `spender_id` may move `amount` of `token_id` from `owner_id`
+    /// under `approval_id`, spending the allowance unless an operator approval covers it.
+    pub(crate) fn use_approval(
+        &mut self,
+        owner_id: &AccountId,
+        token_id: &str,
+        spender_id: &AccountId,
+        approval_id: u64,
+        amount: u128,
+    ) {
+        let approved = self.approved_amount(owner_id, token_id, spender_id, Some(approval_id));
+        require!(
+            approved.is_some_and(|approved| approved >= amount),
+            "Transfer is not approved"
+        );
+        if approved == Some(u128::MAX) {
+            return;
+        }
+
+        let key = allowance_key(owner_id, token_id, spender_id);
+        let mut allowance = self.allowances.get(&key).cloned().unwrap();
+        allowance.amount -= amount;
+        if allowance.amount == 0 {
+            // A spent allowance is removed and its storage refunded to the owner
+            let initial_usage = self.approval_storage_usage();
+            self.allowances.remove(&key);
+            self.release_approval_storage(owner_id, initial_usage);
+        } else {
+            self.allowances.insert(key, allowance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use market_types::Outcome;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::{testing_env, NearToken};
+
+    /// Covers the storage of one allowance or operator approval.
+    const APPROVAL_DEPOSIT: u128 = 10_000_000_000_000_000_000_000;
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn context(predecessor: &str, deposit: u128, timestamp: u64) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account(predecessor))
+            .current_account_id(account("outcome.testnet"))
+            .attached_deposit(NearToken::from_yoctonear(deposit))
+            .block_timestamp(timestamp)
+            .build());
+    }
+
+    fn funded() -> OutcomeToken {
+        context("market.testnet", 0, 0);
+        let mut contract = OutcomeToken::new(account("owner.testnet"), account("market.testnet"));
+        contract.register(&account("alice.testnet"));
+        contract.register(&account("bob.testnet"));
+        contract.mint(0, Outcome::Yes, account("alice.testnet"), U128(1_000));
+        contract.mint(1, Outcome::No, account("alice.testnet"), U128(1_000));
+        contract
+    }
+
+    fn transfer_from_alice(contract: &mut OutcomeToken, token_id: &str, amount: u128, approval_id: u64) {
+        context("vault.testnet", 1, 10);
+        contract.mt_transfer(
+            account("bob.testnet"),
+            token_id.to_string(),
+            U128(amount),
+            Some((account("alice.testnet"), approval_id)),
+            None,
+        );
+    }
+
+    #[test]
+    fn allowance_is_spent_by_transfers() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        let approval_id = contract.mt_approve(
+            "0:yes".to_string(),
+            account("vault.testnet"),
+            U128(300),
+            None,
+        );
+        assert!(get_logs()[0].contains("\"standard\":\"nest-outcome-token\""));
+
+        transfer_from_alice(&mut contract, "0:yes", 200, approval_id);
+        assert!(get_logs().iter().all(|log| log.contains("\"authorized_id\":\"vault.testnet\"")));
+        assert_eq!(contract.mt_balance_of(account("bob.testnet"), "0:yes".to_string()), U128(200));
+        assert_eq!(contract.mt_balance_of(account("alice.testnet"), "0:yes".to_string()), U128(800));
+
+        let remaining = contract
+            .mt_allowance(account("alice.testnet"), "0:yes".to_string(), account("vault.testnet"))
+            .unwrap();
+        assert_eq!(remaining.amount, U128(100));
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfer is not approved")]
+    fn allowance_cannot_be_exceeded() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        let approval_id = contract.mt_approve(
+            "0:yes".to_string(),
+            account("vault.testnet"),
+            U128(300),
+            None,
+        );
+        transfer_from_alice(&mut contract, "0:yes", 301, approval_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfer is not approved")]
+    fn expired_allowance_is_rejected() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        let approval_id = contract.mt_approve(
+            "0:yes".to_string(),
+            account("vault.testnet"),
+            U128(300),
+            Some(U64(5)),
+        );
+        transfer_from_alice(&mut contract, "0:yes", 100, approval_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfer is not approved")]
+    fn revoked_allowance_is_rejected() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        let approval_id = contract.mt_approve(
+            "0:yes".to_string(),
+            account("vault.testnet"),
+            U128(300),
+            None,
+        );
+        context("alice.testnet", 1, 0);
+        contract.mt_revoke("0:yes".to_string(), account("vault.testnet"));
+        transfer_from_alice(&mut contract, "0:yes", 100, approval_id);
+    }
+
+    #[test]
+    fn operator_can_move_any_token() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        let approval_id = contract.mt_set_operator(account("vault.testnet"), Some(U64(100)));
+
+        transfer_from_alice(&mut contract, "0:yes", 1_000, approval_id);
+        transfer_from_alice(&mut contract, "1:no", 500, approval_id);
+        assert_eq!(contract.mt_balance_of(account("bob.testnet"), "1:no".to_string()), U128(500));
+        assert!(contract.mt_is_approved(
+            account("alice.testnet"),
+            vec!["1:no".to_string()],
+            account("vault.testnet"),
+            vec![U128(500)],
+            Some(vec![approval_id]),
+        ));
+
+        context("alice.testnet", 1, 20);
+        contract.mt_revoke_operator(account("vault.testnet"));
+        assert!(contract.mt_operator(account("alice.testnet"), account("vault.testnet")).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Transfer is not approved")]
+    fn stale_approval_id_is_rejected() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        let old_id = contract.mt_set_operator(account("vault.testnet"), None);
+        contract.mt_set_operator(account("vault.testnet"), None);
+        transfer_from_alice(&mut contract, "0:yes", 100, old_id);
+    }
+
+    #[test]
+    #[should_panic(expected = "to cover storage")]
+    fn approval_requires_a_storage_deposit() {
+        let mut contract = funded();
+        context("alice.testnet", 1, 0);
+        contract.mt_approve("0:yes".to_string(), account("vault.testnet"), U128(300), None);
+    }
+
+    #[test]
+    fn replacing_an_approval_needs_one_yocto() {
+        let mut contract = funded();
+        context("alice.testnet", APPROVAL_DEPOSIT, 0);
+        contract.mt_set_operator(account("vault.testnet"), None);
+        context("alice.testnet", 1, 0);
+        let approval_id = contract.mt_set_operator(account("vault.testnet"), None);
+        assert_eq!(
+            contract.mt_operator(account("alice.testnet"), account("vault.testnet")).unwrap().approval_id,
+            approval_id
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::log;
+use near_sdk::serde::Serialize;
+use near_sdk::serde_json::json;
+use near_sdk::AccountId;
+
+use crate::minters::MinterScope;
+
+const NEP245_STANDARD: &str = "nep245";
+const NEP245_VERSION: &str = "1.0.0";
+const NEST_STANDARD: &str = "nest-outcome-token";
+const NEST_VERSION: &str = "1.0.0";
+
+/// NEP-245 multi-token events.
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum Nep245Event<'a> {
+    MtMint {
+        owner_id: &'a AccountId,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        memo: Option<&'a str>,
+    },
+
+    MtBurn {
+        owner_id: &'a AccountId,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        authorized_id: Option<&'a AccountId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        memo: Option<&'a str>,
+    },
+
+    MtTransfer {
+        old_owner_id: &'a AccountId,
+        new_owner_id: &'a AccountId,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        authorized_id: Option<&'a AccountId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        memo: Option<&'a str>,
+    },
+}
+
+impl Nep245Event<'_> {
+    pub fn emit(&self) {
+        let data = json!(self);
+        let event_json = json!({
+            "standard": NEP245_STANDARD,
+            "version": NEP245_VERSION,
+            "event": data["event"],
+            "data": [data["data"]]
+        });
+        log!("EVENT_JSON:{}", event_json);
+    }
+}
+
+/// Outcome-token events outside NEP-245: snapshots, approvals and minter
+/// administration. Balance changes are only reported through NEP-245 events.
+#[derive(Clone, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+pub enum NestEvent<'a> {
+    /// Balances for the market were frozen at settlement.
+    SnapshotTaken {
+        market_id: U64,
+        block_height: U64,
+        yes_supply: U128,
+        no_supply: U128,
+    },
+
+    AllowanceApproved {
+        owner_id: &'a AccountId,
+        spender_id: &'a AccountId,
+        token_id: &'a str,
+        amount: U128,
+        approval_id: u64,
+        expires_at_ns: Option<U64>,
+    },
+
+    AllowanceRevoked {
+        owner_id: &'a AccountId,
+        spender_id: &'a AccountId,
+        token_id: &'a str,
+    },
+
+    OperatorApproved {
+        owner_id: &'a AccountId,
+        operator_id: &'a AccountId,
+        approval_id: u64,
+        expires_at_ns: Option<U64>,
+    },
+
+    OperatorRevoked {
+        owner_id: &'a AccountId,
+        operator_id: &'a AccountId,
+    },
+
+    OwnerChanged {
+        old_owner: &'a AccountId,
+        new_owner: &'a AccountId,
+    },
+
+    MinterAdded {
+        account_id: &'a AccountId,
+        scope: MinterScope,
+    },
+
+    MinterRemoved {
+        account_id: &'a AccountId,
+    },
+}
+
+impl NestEvent<'_> {
+    pub fn emit(&self) {
+        let data = json!(self);
+        let event_json = json!({
+            "standard": NEST_STANDARD,
+            "version": NEST_VERSION,
+            "event": data["event"],
+            "data": [data["data"]]
+        });
+        log!("EVENT_JSON:{}", event_json);
+    }
+}
@@ -0,0 +1,294 @@
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near, require, AccountId};
+
+use market_types::{MarketId, Outcome};
+
+use crate::events::NestEvent;
+use crate::{balance_key, supply_key, OutcomeToken, OutcomeTokenExt};
+
+const DEFAULT_HOLDERS_LIMIT: u32 = 100;
+const MAX_HOLDERS_LIMIT: u32 = 500;
+
+/// Supplies frozen when a market settles. Per-account balances are not stored:
+/// they are rebuilt off-chain from the NEP-245 events up to `block_height`.
+#[near(serializers = [borsh])]
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub taken_at_ns: u64,
+    pub block_height: u64,
+    pub yes_supply: u128,
+    pub no_supply: u128,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct HolderView {
+    pub account_id: AccountId,
+    pub balance: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SnapshotView {
+    pub market_id: U64,
+    pub taken_at_ns: U64,
+    pub block_height: U64,
+    pub yes_supply: U128,
+    pub no_supply: U128,
+}
+
+fn holder_key(market_id: MarketId, outcome: &Outcome, index: u32) -> Vec<u8> {
+    let mut key = supply_key(market_id, outcome);
+    key.extend_from_slice(&index.to_le_bytes());
+    key
+}
+
+#[near]
+impl OutcomeToken {
+    // ── Snapshots ──────────────────────────────────────────────────────
+
+    /// Record the supplies and block height of `market_id` at settlement. Called
+    /// by the market's minter.
+    pub fn snapshot(&mut self, market_id: MarketId) {
+        self.assert_minter(market_id);
+        require!(
+            !self.snapshots.contains_key(&market_id),
+            "Snapshot already taken"
+        );
+
+        let snapshot = Snapshot {
+            taken_at_ns: env::block_timestamp(),
+            block_height: env::block_height(),
+            yes_supply: self.internal_supply(market_id, Outcome::Yes).unwrap_or(0),
+            no_supply: self.internal_supply(market_id, Outcome::No).unwrap_or(0),
+        };
+        NestEvent::SnapshotTaken {
+            market_id: U64(market_id),
+            block_height: U64(snapshot.block_height),
+            yes_supply: U128(snapshot.yes_supply),
+            no_supply: U128(snapshot.no_supply),
+        }
+        .emit();
+        self.snapshots.insert(market_id, snapshot);
+    }
+
+    pub fn get_snapshot(&self, market_id: MarketId) -> Option<SnapshotView> {
+        self.snapshots.get(&market_id).map(|s| SnapshotView {
+            market_id: U64(market_id),
+            taken_at_ns: U64(s.taken_at_ns),
+            block_height: U64(s.block_height),
+            yes_supply: U128(s.yes_supply),
+            no_supply: U128(s.no_supply),
+        })
+    }
+
+    // ── Holder Enumeration ─────────────────────────────────────────────
+
+    /// Number of accounts holding a non-zero balance.
+    pub fn holders_count(&self, market_id: MarketId, outcome: Outcome) -> u32 {
+        self.holder_count
+            .get(&supply_key(market_id, &outcome))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Current holders with a non-zero balance, paginated over the holder set.
+    pub fn holders_of(
+        &self,
+        market_id: MarketId,
+        outcome: Outcome,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<HolderView> {
+        self.holders_page(market_id, outcome, from_index, limit)
+            .into_iter()
+            .filter_map(|account_id| {
+                let balance = self.internal_balance(market_id, outcome, &account_id);
+                (balance > 0).then_some(HolderView {
+                    account_id,
+                    balance: U128(balance),
+                })
+            })
+            .collect()
+    }
+
+    /// Add holders whose balances predate holder tracking, taken from the
+    /// NEP-245 events, so `holders_of` lists them. Accounts without such a
+    /// balance are skipped. Owner only; the contract pays for the entries.
+    /// Returns how many holders were added.
+    pub fn index_legacy_holders(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_ids: Vec<AccountId>,
+    ) -> u32 {
+        self.assert_owner();
+        let mut indexed = 0;
+        for account_id in account_ids {
+            let key = balance_key(market_id, &outcome, &account_id);
+            if self.internal_balance(market_id, outcome, &account_id) > 0
+                && !self.holder_index.contains_key(&key)
+            {
+                self.index_legacy_holder(market_id, outcome, &account_id);
+                indexed += 1;
+            }
+        }
+        indexed
+    }
+}
+
+impl OutcomeToken {
+    /// Index a balance written before holders were tracked. Not charged to the
+    /// account, which never paid for the balance either.
+    pub(crate) fn index_legacy_holder(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_id: &AccountId,
+    ) {
+        self.add_holder(market_id, outcome, account_id);
+        self.legacy_balances
+            .insert(balance_key(market_id, &outcome, account_id));
+    }
+
+    /// Whether the balance at `key` predates storage accounting: it was
+    /// indexed as legacy, or has never been indexed at all.
+    pub(crate) fn is_legacy_balance(&self, key: &[u8]) -> bool {
+        self.legacy_balances.contains(key) || !self.holder_index.contains_key(key)
+    }
+
+    fn holders_page(
+        &self,
+        market_id: MarketId,
+        outcome: Outcome,
+        from_index: Option<u32>,
+        limit: Option<u32>,
+    ) -> Vec<AccountId> {
+        let count = self.holders_count(market_id, outcome);
+        let start = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(DEFAULT_HOLDERS_LIMIT).min(MAX_HOLDERS_LIMIT);
+        (start..count.min(start.saturating_add(limit)))
+            .filter_map(|i| self.holders.get(&holder_key(market_id, &outcome, i)).cloned())
+            .collect()
+    }
+
+    pub(crate) fn add_holder(&mut self, market_id: MarketId, outcome: Outcome, account_id: &AccountId) {
+        let index_key = balance_key(market_id, &outcome, account_id);
+        if self.holder_index.contains_key(&index_key) {
+            return;
+        }
+        let count_key = supply_key(market_id, &outcome);
+        let count = self.holder_count.get(&count_key).copied().unwrap_or(0);
+        self.holders
+            .insert(holder_key(market_id, &outcome, count), account_id.clone());
+        self.holder_index.insert(index_key, count);
+        self.holder_count.insert(count_key, count + 1);
+    }
+
+    /// Swap-remove `account_id` from the holder set.
+    pub(crate) fn remove_holder(&mut self, market_id: MarketId, outcome: Outcome, account_id: &AccountId) {
+        let index_key = balance_key(market_id, &outcome, account_id);
+        let Some(index) = self.holder_index.remove(&index_key) else {
+            return;
+        };
+        let count_key = supply_key(market_id, &outcome);
+        let last = self.holder_count.get(&count_key).copied().unwrap_or(1) - 1;
+        let last_holder = self
+            .holders
+            .remove(&holder_key(market_id, &outcome, last))
+            .expect("Holder set is inconsistent");
+        if index != last {
+            self.holder_index
+                .insert(balance_key(market_id, &outcome, &last_holder), index);
+            self.holders
+                .insert(holder_key(market_id, &outcome, index), last_holder);
+        }
+        if last == 0 {
+            self.holder_count.remove(&count_key);
+        } else {
+            self.holder_count.insert(count_key, last);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn setup() -> OutcomeToken {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account("market.testnet"))
+            .block_height(42)
+            .build());
+        let mut contract = OutcomeToken::new(account("owner.testnet"), account("market.testnet"));
+        for name in ["a.testnet", "b.testnet", "c.testnet", "d.testnet"] {
+            contract.register(&account(name));
+        }
+        contract
+    }
+
+    fn holder_ids(holders: &[HolderView]) -> Vec<&str> {
+        holders.iter().map(|h| h.account_id.as_str()).collect()
+    }
+
+    #[test]
+    fn holders_track_balances() {
+        let mut contract = setup();
+        for name in ["a.testnet", "b.testnet", "c.testnet"] {
+            contract.mint(0, Outcome::Yes, account(name), U128(100));
+        }
+        contract.mint(0, Outcome::No, account("d.testnet"), U128(100));
+        assert_eq!(contract.holders_count(0, Outcome::Yes), 3);
+
+        // Emptying a balance swap-removes the holder
+        contract.burn(0, Outcome::Yes, account("a.testnet"), U128(100));
+        let holders = contract.holders_of(0, Outcome::Yes, None, None);
+        assert_eq!(holder_ids(&holders), vec!["c.testnet", "b.testnet"]);
+        assert_eq!(contract.holders_count(0, Outcome::Yes), 2);
+
+        // Pagination
+        let page = contract.holders_of(0, Outcome::Yes, Some(1), Some(1));
+        assert_eq!(holder_ids(&page), vec!["b.testnet"]);
+        assert_eq!(holder_ids(&contract.holders_of(0, Outcome::No, None, None)), vec!["d.testnet"]);
+    }
+
+    #[test]
+    fn snapshot_records_supplies() {
+        let mut contract = setup();
+        contract.mint(1, Outcome::Yes, account("a.testnet"), U128(300));
+        contract.mint(1, Outcome::Yes, account("b.testnet"), U128(200));
+        contract.snapshot(1);
+
+        // Post-settlement activity still updates the holder set
+        contract.burn(1, Outcome::Yes, account("a.testnet"), U128(300));
+        contract.internal_transfer(1, Outcome::Yes, account("b.testnet"), account("c.testnet"), U128(50));
+
+        let snapshot = contract.get_snapshot(1).unwrap();
+        assert_eq!(snapshot.yes_supply, U128(500));
+        assert_eq!(snapshot.block_height, U64(42));
+        assert!(contract.get_snapshot(0).is_none());
+
+        let current = contract.holders_of(1, Outcome::Yes, None, None);
+        assert_eq!(holder_ids(&current), vec!["b.testnet", "c.testnet"]);
+        assert_eq!(contract.holders_count(1, Outcome::Yes), 2);
+
+        // The emptied account no longer pays for a balance or holder entry
+        let a = contract.storage_accounts.get(&account("a.testnet")).unwrap();
+        assert_eq!(a.used_bytes, contract.account_storage_usage);
+    }
+
+    #[test]
+    #[should_panic(expected = "Snapshot already taken")]
+    fn snapshot_is_taken_once() {
+        let mut contract = setup();
+        contract.snapshot(0);
+        contract.snapshot(0);
+    }
+}
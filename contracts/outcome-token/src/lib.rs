@@ -1,20 +1,82 @@
+mod approvals;
+mod events;
+mod holders;
+mod minters;
+mod mt;
+mod storage;
+
 use near_sdk::json_types::U128;
-use near_sdk::store::LookupMap;
+use near_sdk::store::{IterableMap, LookupMap, LookupSet};
 use near_sdk::{env, near, require, AccountId, PanicOnDefault};
 
-use market_types::{MarketId, Outcome};
+use market_types::{outcome_token_id, MarketId, Outcome, OutcomeBurn};
+
+use approvals::{Allowance, OperatorApproval};
+use events::{NestEvent, Nep245Event};
+use holders::Snapshot;
+use storage::StorageAccount;
+
+pub use approvals::{AllowanceView, OperatorView};
+pub use holders::{HolderView, SnapshotView};
+pub use minters::{MinterScope, MinterView};
+pub use mt::{MtBaseTokenMetadata, MtContractMetadata, MtToken, MtTokenMetadata, MtTokenMetadataAll};
 
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
 pub struct OutcomeToken {
-    /// The market contract authorized to mint/burn
-    market_contract: AccountId,
+    /// Manages the minter set
+    owner: AccountId,
+
+    /// Contracts authorized to mint/burn/move, each scoped to a market-id range
+    minters: IterableMap<AccountId, MinterScope>,
 
     /// Balances: compound key (market_id, outcome, account_id) -> balance
     balances: LookupMap<Vec<u8>, u128>,
 
     /// Total supply per (market_id, outcome)
     supply: LookupMap<Vec<u8>, u128>,
+
+    /// Per-token allowances: (owner, token_id, spender) -> allowance
+    allowances: LookupMap<Vec<u8>, Allowance>,
+
+    /// All-token operators: (owner, operator) -> approval
+    operators: LookupMap<Vec<u8>, OperatorApproval>,
+
+    /// Next id handed out by `mt_approve` / `mt_set_operator`
+    next_approval_id: u64,
+
+    /// Holder set size per (market_id, outcome)
+    holder_count: LookupMap<Vec<u8>, u32>,
+
+    /// Holder set entries: (market_id, outcome, index) -> account
+    holders: LookupMap<Vec<u8>, AccountId>,
+
+    /// Position of each holder in its set: (market_id, outcome, account_id) -> index
+    holder_index: LookupMap<Vec<u8>, u32>,
+
+    /// Settlement snapshots per market
+    snapshots: LookupMap<MarketId, Snapshot>,
+
+    /// NEP-145 storage registrations paying for balance and holder entries
+    storage_accounts: LookupMap<AccountId, StorageAccount>,
+
+    /// Bytes one storage registration occupies
+    account_storage_usage: u64,
+
+    /// Upper bound on the bytes a new balance and its holder entry occupy
+    balance_storage_usage: u64,
+
+    /// Indexed balances that predate storage accounting; the contract paid for
+    /// them and keeps their storage when they are freed
+    legacy_balances: LookupSet<Vec<u8>>,
+}
+
+/// State before minters, holders and storage accounting were added.
+#[near(serializers = [borsh])]
+struct OldOutcomeToken {
+    market_contract: AccountId,
+    balances: LookupMap<Vec<u8>, u128>,
+    supply: LookupMap<Vec<u8>, u128>,
 }
 
 /// Build a storage key for a specific user balance
@@ -37,21 +99,23 @@ fn supply_key(market_id: MarketId, outcome: &Outcome) -> Vec<u8> {
 #[near]
 impl OutcomeToken {
     #[init]
-    pub fn new(market_contract: AccountId) -> Self {
-        Self {
+    pub fn new(owner: AccountId, market_contract: AccountId) -> Self {
+        Self::from_ledger(
+            owner,
             market_contract,
-            balances: LookupMap::new(b"b"),
-            supply: LookupMap::new(b"s"),
-        }
+            LookupMap::new(b"b"),
+            LookupMap::new(b"s"),
+        )
     }
 
-    // ── Authorization ──────────────────────────────────────────────────
-
-    fn assert_market_contract(&self) {
-        require!(
-            env::predecessor_account_id() == self.market_contract,
-            "Only the market contract can call this method"
-        );
+    /// Upgrade from the single market contract layout. The old market contract
+    /// becomes a minter for all markets; existing balances keep contract-paid
+    /// storage, and `index_legacy_holders` adds their holders.
+    #[init(ignore_state)]
+    #[private]
+    pub fn migrate(owner: AccountId) -> Self {
+        let old: OldOutcomeToken = env::state_read().expect("No state to migrate");
+        Self::from_ledger(owner, old.market_contract, old.balances, old.supply)
     }
 
     // ── Authorized Methods ─────────────────────────────────────────────
@@ -63,21 +127,26 @@ impl OutcomeToken {
         account_id: AccountId,
         amount: U128,
     ) {
-        self.assert_market_contract();
+        self.assert_minter(market_id);
         let amount = amount.0;
         if amount == 0 {
             return;
         }
 
-        // Update balance
-        let bkey = balance_key(market_id, &outcome, &account_id);
-        let balance = self.balances.get(&bkey).copied().unwrap_or(0);
-        self.balances.insert(bkey, balance + amount);
+        self.internal_deposit(market_id, outcome, &account_id, amount);
 
         // Update supply
         let skey = supply_key(market_id, &outcome);
         let supply = self.supply.get(&skey).copied().unwrap_or(0);
         self.supply.insert(skey, supply + amount);
+
+        Nep245Event::MtMint {
+            owner_id: &account_id,
+            token_ids: vec![outcome_token_id(market_id, outcome)],
+            amounts: vec![U128(amount)],
+            memo: None,
+        }
+        .emit();
     }
 
     pub fn burn(
@@ -87,23 +156,38 @@ impl OutcomeToken {
         account_id: AccountId,
         amount: U128,
     ) {
-        self.assert_market_contract();
+        let minter = self.assert_minter(market_id);
         let amount = amount.0;
         if amount == 0 {
             return;
         }
 
-        // Update balance
-        let bkey = balance_key(market_id, &outcome, &account_id);
-        let balance = self.balances.get(&bkey).copied().unwrap_or(0);
-        require!(balance >= amount, "Insufficient balance to burn");
-        self.balances.insert(bkey, balance - amount);
+        self.internal_burn(market_id, outcome, &account_id, amount, &minter);
+    }
 
-        // Update supply
-        let skey = supply_key(market_id, &outcome);
-        let supply = self.supply.get(&skey).copied().unwrap_or(0);
-        require!(supply >= amount, "Insufficient supply to burn");
-        self.supply.insert(skey, supply - amount);
+    /// Burn several positions of one account in a single call. Entries without
+    /// an amount burn the whole balance, which also frees its storage; the NEAR
+    /// that storage held is refunded to the account. Entries the caller is not
+    /// the minter for, or that exceed the balance, are skipped instead of
+    /// failing the batch. Returns the amount burned per entry.
+    pub fn burn_batch(&mut self, account_id: AccountId, burns: Vec<OutcomeBurn>) -> Vec<U128> {
+        let minter = env::predecessor_account_id();
+        let used_bytes = self.storage_accounts.get(&account_id).map(|a| a.used_bytes);
+        let burned = burns
+            .into_iter()
+            .map(|burn| {
+                let balance = self.internal_balance(burn.market_id, burn.outcome, &account_id);
+                let amount = burn.amount.map_or(balance, |amount| amount.0);
+                if amount == 0 || amount > balance || !self.is_minter_for(&minter, burn.market_id)
+                {
+                    return U128(0);
+                }
+                self.internal_burn(burn.market_id, burn.outcome, &account_id, amount, &minter);
+                U128(amount)
+            })
+            .collect();
+        self.refund_freed_storage(&account_id, used_bytes);
+        burned
     }
 
     pub fn internal_transfer(
@@ -114,20 +198,30 @@ impl OutcomeToken {
         to: AccountId,
         amount: U128,
     ) {
-        self.assert_market_contract();
+        let minter = self.assert_minter(market_id);
         let amount = amount.0;
         if amount == 0 {
             return;
         }
 
-        let from_key = balance_key(market_id, &outcome, &from);
-        let from_balance = self.balances.get(&from_key).copied().unwrap_or(0);
-        require!(from_balance >= amount, "Insufficient balance to transfer");
-        self.balances.insert(from_key, from_balance - amount);
-
-        let to_key = balance_key(market_id, &outcome, &to);
-        let to_balance = self.balances.get(&to_key).copied().unwrap_or(0);
-        self.balances.insert(to_key, to_balance + amount);
+        self.internal_withdraw(
+            market_id,
+            outcome,
+            &from,
+            amount,
+            "Insufficient balance to transfer",
+        );
+        self.internal_deposit(market_id, outcome, &to, amount);
+
+        Nep245Event::MtTransfer {
+            old_owner_id: &from,
+            new_owner_id: &to,
+            token_ids: vec![outcome_token_id(market_id, outcome)],
+            amounts: vec![U128(amount)],
+            authorized_id: Some(&minter),
+            memo: None,
+        }
+        .emit();
     }
 
     // ── Views ──────────────────────────────────────────────────────────
@@ -138,8 +232,7 @@ impl OutcomeToken {
         outcome: Outcome,
         account_id: AccountId,
     ) -> U128 {
-        let key = balance_key(market_id, &outcome, &account_id);
-        U128(self.balances.get(&key).copied().unwrap_or(0))
+        U128(self.internal_balance(market_id, outcome, &account_id))
     }
 
     pub fn total_supply(&self, market_id: MarketId, outcome: Outcome) -> U128 {
@@ -148,10 +241,144 @@ impl OutcomeToken {
     }
 }
 
+impl OutcomeToken {
+    fn from_ledger(
+        owner: AccountId,
+        market_contract: AccountId,
+        balances: LookupMap<Vec<u8>, u128>,
+        supply: LookupMap<Vec<u8>, u128>,
+    ) -> Self {
+        let mut minters = IterableMap::new(b"m");
+        minters.insert(market_contract.clone(), MinterScope::ALL);
+        NestEvent::MinterAdded {
+            account_id: &market_contract,
+            scope: MinterScope::ALL,
+        }
+        .emit();
+
+        let mut this = Self {
+            owner,
+            minters,
+            balances,
+            supply,
+            allowances: LookupMap::new(b"a"),
+            operators: LookupMap::new(b"p"),
+            next_approval_id: 0,
+            holder_count: LookupMap::new(b"c"),
+            holders: LookupMap::new(b"h"),
+            holder_index: LookupMap::new(b"i"),
+            snapshots: LookupMap::new(b"n"),
+            storage_accounts: LookupMap::new(b"r"),
+            account_storage_usage: 0,
+            balance_storage_usage: 0,
+            legacy_balances: LookupSet::new(b"l"),
+        };
+        this.account_storage_usage = this.measure_account_storage_usage();
+        this.balance_storage_usage = this.measure_balance_storage_usage();
+        this
+    }
+
+    // ── Balance Helpers ────────────────────────────────────────────────
+
+    pub(crate) fn internal_deposit(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_id: &AccountId,
+        amount: u128,
+    ) {
+        let key = balance_key(market_id, &outcome, account_id);
+        let balance = self.balances.get(&key).copied().unwrap_or(0);
+        if balance > 0 && !self.holder_index.contains_key(&key) {
+            self.index_legacy_holder(market_id, outcome, account_id);
+        }
+        let initial_usage = self.storage_usage();
+        self.balances.insert(key, balance + amount);
+        if balance == 0 && amount > 0 {
+            self.add_holder(market_id, outcome, account_id);
+        }
+        self.settle_storage(account_id, initial_usage);
+    }
+
+    pub(crate) fn internal_withdraw(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_id: &AccountId,
+        amount: u128,
+        insufficient_msg: &str,
+    ) {
+        let key = balance_key(market_id, &outcome, account_id);
+        let balance = self.balances.get(&key).copied().unwrap_or(0);
+        require!(balance >= amount, insufficient_msg);
+        if balance == amount && self.is_legacy_balance(&key) {
+            // The contract paid for this balance, so it keeps the freed storage
+            self.legacy_balances.remove(&key);
+            self.balances.remove(&key);
+            self.remove_holder(market_id, outcome, account_id);
+            return;
+        }
+        let initial_usage = self.storage_usage();
+        if balance == amount {
+            self.balances.remove(&key);
+            self.remove_holder(market_id, outcome, account_id);
+        } else {
+            self.balances.insert(key, balance - amount);
+        }
+        self.settle_storage(account_id, initial_usage);
+    }
+
+    pub(crate) fn internal_burn(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_id: &AccountId,
+        amount: u128,
+        minter: &AccountId,
+    ) {
+        self.internal_withdraw(
+            market_id,
+            outcome,
+            account_id,
+            amount,
+            "Insufficient balance to burn",
+        );
+
+        // Update supply
+        let skey = supply_key(market_id, &outcome);
+        let supply = self.supply.get(&skey).copied().unwrap_or(0);
+        require!(supply >= amount, "Insufficient supply to burn");
+        self.supply.insert(skey, supply - amount);
+
+        Nep245Event::MtBurn {
+            owner_id: account_id,
+            token_ids: vec![outcome_token_id(market_id, outcome)],
+            amounts: vec![U128(amount)],
+            authorized_id: Some(minter),
+            memo: None,
+        }
+        .emit();
+    }
+
+    pub(crate) fn internal_balance(
+        &self,
+        market_id: MarketId,
+        outcome: Outcome,
+        account_id: &AccountId,
+    ) -> u128 {
+        let key = balance_key(market_id, &outcome, account_id);
+        self.balances.get(&key).copied().unwrap_or(0)
+    }
+
+    pub(crate) fn internal_supply(&self, market_id: MarketId, outcome: Outcome) -> Option<u128> {
+        self.supply.get(&supply_key(market_id, &outcome)).copied()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
     use near_sdk::testing_env;
 
     fn market_account() -> AccountId {
@@ -171,7 +398,10 @@ mod tests {
             .predecessor_account_id(market_account())
             .build();
         testing_env!(context);
-        OutcomeToken::new(market_account())
+        let mut contract = OutcomeToken::new("owner.testnet".parse().unwrap(), market_account());
+        contract.register(&alice());
+        contract.register(&bob());
+        contract
     }
 
     #[test]
@@ -192,6 +422,22 @@ mod tests {
         assert_eq!(contract.total_supply(0, Outcome::Yes), U128(600_000));
     }
 
+    #[test]
+    fn test_mint_burn_emit_only_nep245_events() {
+        let mut contract = setup();
+        contract.mint(3, Outcome::No, bob(), U128(300));
+        contract.burn(3, Outcome::No, bob(), U128(200));
+        let logs = get_logs();
+        let balance_logs: Vec<&String> = logs
+            .iter()
+            .filter(|log| log.contains("\"token_ids\":[\"3:no\"]"))
+            .collect();
+        assert_eq!(balance_logs.len(), 2);
+        assert!(balance_logs[0].contains("\"event\":\"mt_mint\""));
+        assert!(balance_logs[1].contains("\"event\":\"mt_burn\""));
+        assert!(balance_logs.iter().all(|log| log.contains("\"standard\":\"nep245\"")));
+    }
+
     #[test]
     #[should_panic(expected = "Insufficient balance to burn")]
     fn test_burn_insufficient() {
@@ -200,6 +446,64 @@ mod tests {
         contract.burn(0, Outcome::Yes, alice(), U128(200));
     }
 
+    #[test]
+    fn test_burn_batch_skips_entries_it_cannot_burn() {
+        let mut contract = setup();
+        contract.mint(0, Outcome::Yes, alice(), U128(500));
+        contract.mint(1, Outcome::No, alice(), U128(300));
+        let burned = contract.burn_batch(
+            alice(),
+            vec![
+                OutcomeBurn {
+                    market_id: 0,
+                    outcome: Outcome::Yes,
+                    amount: Some(U128(500)),
+                },
+                OutcomeBurn {
+                    market_id: 1,
+                    outcome: Outcome::No,
+                    amount: Some(U128(400)),
+                },
+            ],
+        );
+        assert_eq!(burned, vec![U128(500), U128(0)]);
+        assert_eq!(contract.balance_of(0, Outcome::Yes, alice()), U128(0));
+        assert_eq!(contract.balance_of(1, Outcome::No, alice()), U128(300));
+        assert_eq!(contract.total_supply(0, Outcome::Yes), U128(0));
+    }
+
+    #[test]
+    fn test_burn_batch_whole_balance() {
+        let mut contract = setup();
+        contract.mint(2, Outcome::No, alice(), U128(300));
+        contract.mint(2, Outcome::No, bob(), U128(100));
+        let burned = contract.burn_batch(
+            alice(),
+            vec![
+                OutcomeBurn {
+                    market_id: 2,
+                    outcome: Outcome::No,
+                    amount: None,
+                },
+                OutcomeBurn {
+                    market_id: 2,
+                    outcome: Outcome::Yes,
+                    amount: None,
+                },
+            ],
+        );
+        assert_eq!(burned, vec![U128(300), U128(0)]);
+        assert_eq!(contract.total_supply(2, Outcome::No), U128(100));
+        // The emptied balance is removed from storage and its deposit refunded
+        assert!(!contract
+            .balances
+            .contains_key(&balance_key(2, &Outcome::No, &alice())));
+        let account = contract.storage_accounts.get(&alice()).unwrap();
+        assert_eq!(account.used_bytes, contract.account_storage_usage);
+        assert!(account.deposit < 10u128.pow(24));
+        assert_eq!(near_sdk::test_utils::get_created_receipts().len(), 1);
+    }
+
     #[test]
     fn test_internal_transfer() {
         let mut contract = setup();
@@ -210,7 +514,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Only the market contract")]
+    #[should_panic(expected = "Only the minter for this market")]
     fn test_unauthorized_mint() {
         let mut contract = setup();
         let context = VMContextBuilder::new()
@@ -228,4 +532,55 @@ mod tests {
         assert_eq!(contract.balance_of(0, Outcome::Yes, alice()), U128(100));
         assert_eq!(contract.balance_of(1, Outcome::Yes, alice()), U128(200));
     }
+
+    #[test]
+    fn migrate_grandfathers_legacy_balances() {
+        use near_contract_standards::storage_management::StorageManagement;
+
+        let context = |predecessor: &str| {
+            testing_env!(VMContextBuilder::new()
+                .current_account_id("outcome.testnet".parse().unwrap())
+                .predecessor_account_id(predecessor.parse().unwrap())
+                .build());
+        };
+        context("outcome.testnet");
+        let mut balances = LookupMap::new(b"b");
+        balances.insert(balance_key(0, &Outcome::Yes, &alice()), 500u128);
+        balances.insert(balance_key(0, &Outcome::Yes, &bob()), 300u128);
+        balances.flush();
+        let mut supply = LookupMap::new(b"s");
+        supply.insert(supply_key(0, &Outcome::Yes), 800u128);
+        supply.flush();
+        env::state_write(&OldOutcomeToken {
+            market_contract: market_account(),
+            balances,
+            supply,
+        });
+
+        let mut contract = OutcomeToken::migrate("owner.testnet".parse().unwrap());
+        assert_eq!(contract.minter_of(7), Some(market_account()));
+        assert_eq!(contract.balance_of(0, Outcome::Yes, alice()), U128(500));
+        assert_eq!(contract.total_supply(0, Outcome::Yes), U128(800));
+
+        // Legacy holders are listed once indexed by the owner or on their next deposit
+        context("owner.testnet");
+        assert_eq!(contract.index_legacy_holders(0, Outcome::Yes, vec![alice(), alice()]), 1);
+        context("market.testnet");
+        contract.mint(0, Outcome::Yes, bob(), U128(100));
+        let holders: Vec<AccountId> = contract
+            .holders_of(0, Outcome::Yes, None, None)
+            .into_iter()
+            .map(|holder| holder.account_id)
+            .collect();
+        assert_eq!(holders, vec![alice(), bob()]);
+
+        // Freeing a legacy balance neither needs a registration nor credits one
+        contract.register(&alice());
+        contract.mint(1, Outcome::No, alice(), U128(10));
+        let available = contract.storage_balance_of(alice()).unwrap().available;
+        contract.burn(0, Outcome::Yes, alice(), U128(500));
+        contract.burn(0, Outcome::Yes, bob(), U128(400));
+        assert_eq!(contract.holders_count(0, Outcome::Yes), 0);
+        assert_eq!(contract.storage_balance_of(alice()).unwrap().available, available);
+    }
 }
@@ -0,0 +1,222 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{env, near, require, AccountId};
+
+use market_types::MarketId;
+
+use crate::events::NestEvent;
+use crate::{OutcomeToken, OutcomeTokenExt};
+
+/// Market ids a minter may mint, burn and move: `start..end`, unbounded when `end` is `None`.
+#[near(serializers = [borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinterScope {
+    pub start_market_id: MarketId,
+    pub end_market_id: Option<MarketId>,
+}
+
+impl MinterScope {
+    pub const ALL: MinterScope = MinterScope {
+        start_market_id: 0,
+        end_market_id: None,
+    };
+
+    pub fn contains(&self, market_id: MarketId) -> bool {
+        market_id >= self.start_market_id && self.end_market_id.is_none_or(|end| market_id < end)
+    }
+
+    fn overlaps(&self, other: &MinterScope) -> bool {
+        self.end_market_id.is_none_or(|end| other.start_market_id < end)
+            && other.end_market_id.is_none_or(|end| self.start_market_id < end)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MinterView {
+    pub account_id: AccountId,
+    pub scope: MinterScope,
+}
+
+#[near]
+impl OutcomeToken {
+    // ── Owner Controls ─────────────────────────────────────────────────
+
+    pub fn set_owner(&mut self, new_owner: AccountId) {
+        self.assert_owner();
+        NestEvent::OwnerChanged {
+            old_owner: &self.owner,
+            new_owner: &new_owner,
+        }
+        .emit();
+        self.owner = new_owner;
+    }
+
+    /// Authorize `account_id` for the market ids in `scope`. Scopes may not
+    /// overlap, so each market has at most one minter; re-adding an existing
+    /// minter replaces its scope.
+    pub fn add_minter(&mut self, account_id: AccountId, scope: MinterScope) {
+        self.assert_owner();
+        require!(
+            scope
+                .end_market_id
+                .is_none_or(|end| end > scope.start_market_id),
+            "Minter scope must not be empty"
+        );
+        require!(
+            self.minters
+                .iter()
+                .all(|(minter, existing)| *minter == account_id || !existing.overlaps(&scope)),
+            "Minter scope overlaps an existing minter"
+        );
+
+        self.minters.insert(account_id.clone(), scope);
+        NestEvent::MinterAdded {
+            account_id: &account_id,
+            scope,
+        }
+        .emit();
+    }
+
+    pub fn remove_minter(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        require!(
+            self.minters.remove(&account_id).is_some(),
+            "Account is not a minter"
+        );
+        NestEvent::MinterRemoved {
+            account_id: &account_id,
+        }
+        .emit();
+    }
+
+    // ── Views ──────────────────────────────────────────────────────────
+
+    pub fn get_owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    pub fn get_minters(&self) -> Vec<MinterView> {
+        self.minters
+            .iter()
+            .map(|(account_id, scope)| MinterView {
+                account_id: account_id.clone(),
+                scope: *scope,
+            })
+            .collect()
+    }
+
+    /// The minter authorized for `market_id`, if any.
+    pub fn minter_of(&self, market_id: MarketId) -> Option<AccountId> {
+        self.minters
+            .iter()
+            .find(|(_, scope)| scope.contains(market_id))
+            .map(|(account_id, _)| account_id.clone())
+    }
+}
+
+impl OutcomeToken {
+    pub(crate) fn assert_owner(&self) {
+        require!(
+            env::predecessor_account_id() == self.owner,
+            "Only the owner can call this method"
+        );
+    }
+
+    /// Require the caller to be the minter for `market_id`. Returns the caller.
+    pub(crate) fn assert_minter(&self, market_id: MarketId) -> AccountId {
+        let caller = env::predecessor_account_id();
+        require!(
+            self.is_minter_for(&caller, market_id),
+            "Only the minter for this market can call this method"
+        );
+        caller
+    }
+
+    pub(crate) fn is_minter_for(&self, account_id: &AccountId, market_id: MarketId) -> bool {
+        self.minters
+            .get(account_id)
+            .is_some_and(|scope| scope.contains(market_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use market_types::Outcome;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn context(predecessor: &str) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account(predecessor))
+            .build());
+    }
+
+    fn scope(start: MarketId, end: Option<MarketId>) -> MinterScope {
+        MinterScope {
+            start_market_id: start,
+            end_market_id: end,
+        }
+    }
+
+    /// Market contract v1 owns ids 0..100, the scalar contract owns 1000+.
+    fn setup() -> OutcomeToken {
+        context("owner.testnet");
+        let mut contract = OutcomeToken::new(account("owner.testnet"), account("market-v1.testnet"));
+        contract.add_minter(account("market-v1.testnet"), scope(0, Some(100)));
+        contract.add_minter(account("scalar.testnet"), scope(1_000, None));
+        contract.register(&account("alice.testnet"));
+        contract
+    }
+
+    #[test]
+    fn minters_are_scoped_to_their_markets() {
+        let mut contract = setup();
+        assert!(get_logs()[1].contains("\"event\":\"minter_added\""));
+
+        context("scalar.testnet");
+        contract.mint(1_500, Outcome::Yes, account("alice.testnet"), U128(10));
+        assert_eq!(contract.minter_of(1_500), Some(account("scalar.testnet")));
+        assert_eq!(contract.minter_of(500), None);
+        assert_eq!(contract.get_minters().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the minter for this market can call this method")]
+    fn minter_cannot_touch_other_namespace() {
+        let mut contract = setup();
+        context("scalar.testnet");
+        contract.mint(5, Outcome::Yes, account("alice.testnet"), U128(10));
+    }
+
+    #[test]
+    fn market_contract_can_be_rotated() {
+        let mut contract = setup();
+        contract.remove_minter(account("market-v1.testnet"));
+        contract.add_minter(account("market-v2.testnet"), scope(0, Some(100)));
+
+        context("market-v2.testnet");
+        contract.mint(7, Outcome::No, account("alice.testnet"), U128(10));
+        assert_eq!(contract.balance_of(7, Outcome::No, account("alice.testnet")), U128(10));
+    }
+
+    #[test]
+    #[should_panic(expected = "Minter scope overlaps an existing minter")]
+    fn overlapping_scopes_are_rejected() {
+        let mut contract = setup();
+        contract.add_minter(account("other.testnet"), scope(50, Some(150)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn only_owner_manages_minters() {
+        let mut contract = setup();
+        context("scalar.testnet");
+        contract.remove_minter(account("market-v1.testnet"));
+    }
+}
@@ -0,0 +1,650 @@
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{
+    assert_one_yocto, env, near, require, AccountId, Gas, GasWeight, NearToken, Promise,
+    PromiseOrValue, PromiseResult,
+};
+
+use market_types::{
+    outcome_token_id, parse_outcome_token_id, MarketId, Outcome, USDC_DECIMALS,
+};
+
+use crate::events::Nep245Event;
+use crate::{OutcomeToken, OutcomeTokenExt};
+
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas::from_tgas(15);
+const GAS_FOR_MT_TRANSFER_CALL: Gas = Gas::from_tgas(30);
+
+const MT_METADATA_SPEC: &str = "mt-1.0.0";
+
+// ── NEP-245 Types ──────────────────────────────────────────────────────
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtToken {
+    pub token_id: String,
+    pub owner_id: Option<AccountId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtContractMetadata {
+    pub spec: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtBaseTokenMetadata {
+    pub name: String,
+    pub id: String,
+    pub symbol: Option<String>,
+    pub icon: Option<String>,
+    pub decimals: Option<String>,
+    pub base_uri: Option<String>,
+    pub reference: Option<String>,
+    pub copies: Option<u64>,
+    pub reference_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtTokenMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<String>,
+    pub issued_at: Option<String>,
+    pub expires_at: Option<String>,
+    pub starts_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MtTokenMetadataAll {
+    pub base: MtBaseTokenMetadata,
+    pub token: MtTokenMetadata,
+}
+
+pub(crate) fn parse_token_id(token_id: &str) -> (MarketId, Outcome) {
+    parse_outcome_token_id(token_id)
+        .unwrap_or_else(|| env::panic_str(&format!("Invalid token id: {token_id}")))
+}
+
+fn outcome_label(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Yes => "YES",
+        Outcome::No => "NO",
+    }
+}
+
+#[near]
+impl OutcomeToken {
+    // ── NEP-245 Core ───────────────────────────────────────────────────
+
+    /// Transfer an outcome position to another account. Requires exactly 1 yoctoNEAR.
+    #[payable]
+    pub fn mt_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: String,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+        self.internal_mt_transfer(
+            &sender_id,
+            &receiver_id,
+            &[token_id],
+            &[amount],
+            approval.map(|a| vec![Some(a)]),
+            memo.as_deref(),
+        );
+    }
+
+    #[payable]
+    pub fn mt_batch_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+        self.internal_mt_transfer(
+            &sender_id,
+            &receiver_id,
+            &token_ids,
+            &amounts,
+            approvals,
+            memo.as_deref(),
+        );
+    }
+
+    /// Transfer and call `mt_on_transfer` on the receiver. Amounts the receiver
+    /// reports as unused are refunded to the previous owner.
+    #[payable]
+    pub fn mt_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: String,
+        amount: U128,
+        approval: Option<(AccountId, u64)>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        self.mt_batch_transfer_call(
+            receiver_id,
+            vec![token_id],
+            vec![amount],
+            approval.map(|a| vec![Some(a)]),
+            memo,
+            msg,
+        )
+    }
+
+    #[payable]
+    pub fn mt_batch_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        assert_one_yocto();
+        require!(
+            env::prepaid_gas() > GAS_FOR_MT_TRANSFER_CALL.saturating_add(GAS_FOR_RESOLVE_TRANSFER),
+            "More gas is required"
+        );
+        let sender_id = env::predecessor_account_id();
+        let previous_owner_ids = self.internal_mt_transfer(
+            &sender_id,
+            &receiver_id,
+            &token_ids,
+            &amounts,
+            approvals.clone(),
+            memo.as_deref(),
+        );
+
+        Promise::new(receiver_id.clone())
+            .function_call_weight(
+                "mt_on_transfer".to_string(),
+                near_sdk::serde_json::json!({
+                    "sender_id": sender_id,
+                    "previous_owner_ids": previous_owner_ids,
+                    "token_ids": token_ids,
+                    "amounts": amounts,
+                    "msg": msg,
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_MT_TRANSFER_CALL,
+                GasWeight(1),
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "mt_resolve_transfer".to_string(),
+                    near_sdk::serde_json::json!({
+                        "previous_owner_ids": previous_owner_ids,
+                        "receiver_id": receiver_id,
+                        "token_ids": token_ids,
+                        "amounts": amounts,
+                        "approvals": approvals,
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_RESOLVE_TRANSFER,
+                ),
+            )
+            .into()
+    }
+
+    /// Refund whatever the receiver did not use. Returns the amounts kept by the receiver.
+    #[private]
+    pub fn mt_resolve_transfer(
+        &mut self,
+        previous_owner_ids: Vec<AccountId>,
+        receiver_id: AccountId,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        #[allow(unused_variables)] approvals: Option<Vec<Option<(AccountId, u64)>>>,
+    ) -> Vec<U128> {
+        let unused: Vec<U128> = match env::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                near_sdk::serde_json::from_slice::<Vec<U128>>(&bytes)
+                    .ok()
+                    .filter(|unused| unused.len() == amounts.len())
+                    .unwrap_or_else(|| amounts.clone())
+            }
+            // Receiver failed — refund everything
+            _ => amounts.clone(),
+        };
+
+        let mut kept = Vec::with_capacity(amounts.len());
+        for (i, token_id) in token_ids.iter().enumerate() {
+            let (market_id, outcome) = parse_token_id(token_id);
+            let amount = amounts[i].0;
+            let unused = unused[i].0.min(amount);
+            let receiver_balance = self.internal_balance(market_id, outcome, &receiver_id);
+            let previous_owner = &previous_owner_ids[i];
+            // A sender that unregistered or withdrew its storage balance meanwhile
+            // can't be refunded; the receiver keeps the tokens
+            let refund = if self.can_receive(market_id, outcome, previous_owner) {
+                unused.min(receiver_balance)
+            } else {
+                if unused > 0 {
+                    env::log_str(&format!(
+                        "Refund of {unused} {token_id} to {previous_owner} is not covered by its storage balance, kept by {receiver_id}"
+                    ));
+                }
+                0
+            };
+
+            if refund > 0 {
+                self.internal_withdraw(
+                    market_id,
+                    outcome,
+                    &receiver_id,
+                    refund,
+                    "Insufficient balance to refund",
+                );
+                self.internal_deposit(market_id, outcome, previous_owner, refund);
+
+                Nep245Event::MtTransfer {
+                    old_owner_id: &receiver_id,
+                    new_owner_id: previous_owner,
+                    token_ids: vec![token_id.clone()],
+                    amounts: vec![U128(refund)],
+                    authorized_id: None,
+                    memo: Some("refund"),
+                }
+                .emit();
+            }
+            kept.push(U128(amount - refund));
+        }
+        kept
+    }
+
+    // ── NEP-245 Views ──────────────────────────────────────────────────
+
+    /// Outcome tokens are fungible, so `owner_id` is always `None`.
+    pub fn mt_token(&self, token_ids: Vec<String>) -> Vec<Option<MtToken>> {
+        token_ids
+            .into_iter()
+            .map(|token_id| {
+                let (market_id, outcome) = parse_outcome_token_id(&token_id)?;
+                self.internal_supply(market_id, outcome)?;
+                Some(MtToken {
+                    token_id,
+                    owner_id: None,
+                })
+            })
+            .collect()
+    }
+
+    pub fn mt_balance_of(&self, account_id: AccountId, token_id: String) -> U128 {
+        let (market_id, outcome) = parse_token_id(&token_id);
+        U128(self.internal_balance(market_id, outcome, &account_id))
+    }
+
+    pub fn mt_batch_balance_of(&self, account_id: AccountId, token_ids: Vec<String>) -> Vec<U128> {
+        token_ids
+            .iter()
+            .map(|token_id| {
+                let (market_id, outcome) = parse_token_id(token_id);
+                U128(self.internal_balance(market_id, outcome, &account_id))
+            })
+            .collect()
+    }
+
+    pub fn mt_supply(&self, token_id: String) -> Option<U128> {
+        let (market_id, outcome) = parse_outcome_token_id(&token_id)?;
+        self.internal_supply(market_id, outcome).map(U128)
+    }
+
+    pub fn mt_batch_supply(&self, token_ids: Vec<String>) -> Vec<Option<U128>> {
+        token_ids
+            .into_iter()
+            .map(|token_id| self.mt_supply(token_id))
+            .collect()
+    }
+
+    // ── NEP-245 Metadata ───────────────────────────────────────────────
+
+    pub fn mt_metadata_contract(&self) -> MtContractMetadata {
+        MtContractMetadata {
+            spec: MT_METADATA_SPEC.to_string(),
+            name: "Nest Markets Outcome Tokens".to_string(),
+        }
+    }
+
+    pub fn mt_metadata_token_all(&self, token_ids: Vec<String>) -> Vec<Option<MtTokenMetadataAll>> {
+        token_ids
+            .into_iter()
+            .map(|token_id| {
+                let (market_id, outcome) = parse_outcome_token_id(&token_id)?;
+                Some(MtTokenMetadataAll {
+                    base: Self::base_metadata(market_id, outcome),
+                    token: Self::token_metadata(market_id, outcome),
+                })
+            })
+            .collect()
+    }
+
+    pub fn mt_metadata_token_by_token_id(
+        &self,
+        token_ids: Vec<String>,
+    ) -> Vec<Option<MtTokenMetadata>> {
+        token_ids
+            .into_iter()
+            .map(|token_id| {
+                let (market_id, outcome) = parse_outcome_token_id(&token_id)?;
+                Some(Self::token_metadata(market_id, outcome))
+            })
+            .collect()
+    }
+
+    pub fn mt_metadata_base_by_token_id(
+        &self,
+        token_ids: Vec<String>,
+    ) -> Vec<Option<MtBaseTokenMetadata>> {
+        token_ids
+            .into_iter()
+            .map(|token_id| {
+                let (market_id, outcome) = parse_outcome_token_id(&token_id)?;
+                Some(Self::base_metadata(market_id, outcome))
+            })
+            .collect()
+    }
+}
+
+impl OutcomeToken {
+    /// Move balances to `receiver_id`. Each token is taken from the sender, or from
+    /// the owner named in its approval when the sender is an approved spender.
+    /// Returns the previous owner of each token.
+    pub(crate) fn internal_mt_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_ids: &[String],
+        amounts: &[U128],
+        approvals: Option<Vec<Option<(AccountId, u64)>>>,
+        memo: Option<&str>,
+    ) -> Vec<AccountId> {
+        require!(!token_ids.is_empty(), "Token ids cannot be empty");
+        require!(
+            token_ids.len() == amounts.len(),
+            "Token ids and amounts must have the same length"
+        );
+        let approvals = approvals.unwrap_or_else(|| vec![None; token_ids.len()]);
+        require!(
+            approvals.len() == token_ids.len(),
+            "Token ids and approvals must have the same length"
+        );
+
+        let mut previous_owner_ids = Vec::with_capacity(token_ids.len());
+        for ((token_id, amount), approval) in token_ids.iter().zip(amounts).zip(&approvals) {
+            require!(amount.0 > 0, "Amount must be positive");
+            let (market_id, outcome) = parse_token_id(token_id);
+            let owner_id = match approval {
+                Some((owner_id, approval_id)) if owner_id != sender_id => {
+                    self.use_approval(owner_id, token_id, sender_id, *approval_id, amount.0);
+                    owner_id
+                }
+                _ => sender_id,
+            };
+            require!(owner_id != receiver_id, "Sender and receiver must differ");
+
+            self.internal_withdraw(
+                market_id,
+                outcome,
+                owner_id,
+                amount.0,
+                "Insufficient balance to transfer",
+            );
+            self.internal_deposit(market_id, outcome, receiver_id, amount.0);
+            previous_owner_ids.push(owner_id.clone());
+        }
+
+        // One event per run of tokens sharing the same owner and authorization
+        let mut start = 0;
+        while start < token_ids.len() {
+            let owner_id = &previous_owner_ids[start];
+            let end = (start..token_ids.len())
+                .find(|&i| previous_owner_ids[i] != *owner_id)
+                .unwrap_or(token_ids.len());
+            Nep245Event::MtTransfer {
+                old_owner_id: owner_id,
+                new_owner_id: receiver_id,
+                token_ids: token_ids[start..end].to_vec(),
+                amounts: amounts[start..end].to_vec(),
+                authorized_id: (owner_id != sender_id).then_some(sender_id),
+                memo,
+            }
+            .emit();
+            start = end;
+        }
+
+        previous_owner_ids
+    }
+
+    fn base_metadata(market_id: MarketId, outcome: Outcome) -> MtBaseTokenMetadata {
+        MtBaseTokenMetadata {
+            name: format!("Nest Market #{market_id} {}", outcome_label(outcome)),
+            id: outcome_token_id(market_id, outcome),
+            symbol: Some(format!("NEST-{market_id}-{}", outcome_label(outcome))),
+            icon: None,
+            decimals: Some(USDC_DECIMALS.to_string()),
+            base_uri: None,
+            reference: None,
+            copies: None,
+            reference_hash: None,
+        }
+    }
+
+    fn token_metadata(market_id: MarketId, outcome: Outcome) -> MtTokenMetadata {
+        MtTokenMetadata {
+            title: Some(format!("Market #{market_id}: {}", outcome_label(outcome))),
+            description: Some(format!(
+                "Redeems 1:1 for collateral if market #{market_id} settles {}",
+                outcome_label(outcome)
+            )),
+            media: None,
+            media_hash: None,
+            issued_at: None,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: None,
+            reference_hash: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_contract_standards::storage_management::StorageManagement;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn context(predecessor: &str, deposit: u128) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account(predecessor))
+            .current_account_id(account("outcome.testnet"))
+            .attached_deposit(NearToken::from_yoctonear(deposit))
+            .build());
+    }
+
+    fn funded() -> OutcomeToken {
+        context("market.testnet", 0);
+        let mut contract = OutcomeToken::new(account("owner.testnet"), account("market.testnet"));
+        contract.register(&account("alice.testnet"));
+        contract.register(&account("bob.testnet"));
+        contract.register(&account("vault.testnet"));
+        contract.mint(0, Outcome::Yes, account("alice.testnet"), U128(1_000));
+        contract.mint(0, Outcome::No, account("alice.testnet"), U128(500));
+        contract
+    }
+
+    #[test]
+    fn token_ids_round_trip() {
+        assert_eq!(outcome_token_id(7, Outcome::Yes), "7:yes");
+        assert_eq!(parse_outcome_token_id("7:no"), Some((7, Outcome::No)));
+        assert_eq!(parse_outcome_token_id("7:maybe"), None);
+        assert_eq!(parse_outcome_token_id("x:yes"), None);
+        assert_eq!(parse_outcome_token_id("07:yes"), None);
+        assert_eq!(parse_outcome_token_id("+7:yes"), None);
+    }
+
+    #[test]
+    fn mt_transfer_moves_balance_and_emits_event() {
+        let mut contract = funded();
+        context("alice.testnet", 1);
+
+        contract.mt_transfer(account("bob.testnet"), "0:yes".to_string(), U128(400), None, None);
+
+        assert_eq!(contract.mt_balance_of(account("alice.testnet"), "0:yes".to_string()), U128(600));
+        assert_eq!(contract.mt_balance_of(account("bob.testnet"), "0:yes".to_string()), U128(400));
+        assert_eq!(contract.mt_supply("0:yes".to_string()), Some(U128(1_000)));
+        let logs = get_logs();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].contains("\"standard\":\"nep245\""));
+        assert!(logs[0].contains("\"event\":\"mt_transfer\""));
+    }
+
+    #[test]
+    fn mt_batch_transfer_moves_both_outcomes() {
+        let mut contract = funded();
+        context("alice.testnet", 1);
+
+        contract.mt_batch_transfer(
+            account("bob.testnet"),
+            vec!["0:yes".to_string(), "0:no".to_string()],
+            vec![U128(100), U128(200)],
+            None,
+            None,
+        );
+
+        assert_eq!(
+            contract.mt_batch_balance_of(
+                account("bob.testnet"),
+                vec!["0:yes".to_string(), "0:no".to_string()]
+            ),
+            vec![U128(100), U128(200)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+    fn mt_transfer_requires_one_yocto() {
+        let mut contract = funded();
+        context("alice.testnet", 0);
+        contract.mt_transfer(account("bob.testnet"), "0:yes".to_string(), U128(1), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient balance to transfer")]
+    fn mt_transfer_rejects_overdraft() {
+        let mut contract = funded();
+        context("alice.testnet", 1);
+        contract.mt_transfer(account("bob.testnet"), "0:yes".to_string(), U128(1_001), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not registered, call storage_deposit first")]
+    fn mt_transfer_rejects_unregistered_receiver() {
+        let mut contract = funded();
+        context("alice.testnet", 1);
+        contract.mt_transfer(account("carol.testnet"), "0:yes".to_string(), U128(100), None, None);
+    }
+
+    #[test]
+    fn resolve_transfer_refunds_unused_amounts() {
+        let mut contract = funded();
+        context("alice.testnet", 1);
+        contract.mt_transfer(account("vault.testnet"), "0:yes".to_string(), U128(300), None, None);
+
+        testing_env!(
+            VMContextBuilder::new()
+                .predecessor_account_id(account("outcome.testnet"))
+                .current_account_id(account("outcome.testnet"))
+                .build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(b"[\"100\"]".to_vec())]
+        );
+        let kept = contract.mt_resolve_transfer(
+            vec![account("alice.testnet")],
+            account("vault.testnet"),
+            vec!["0:yes".to_string()],
+            vec![U128(300)],
+            None,
+        );
+
+        assert_eq!(kept, vec![U128(200)]);
+        assert_eq!(contract.mt_balance_of(account("alice.testnet"), "0:yes".to_string()), U128(800));
+        assert_eq!(contract.mt_balance_of(account("vault.testnet"), "0:yes".to_string()), U128(200));
+    }
+
+    #[test]
+    fn resolve_transfer_keeps_tokens_the_sender_cannot_store() {
+        let mut contract = funded();
+        context("alice.testnet", 1);
+        contract.mt_transfer(account("vault.testnet"), "0:yes".to_string(), U128(1_000), None, None);
+        // The sender withdraws the storage balance its emptied entry freed
+        context("alice.testnet", 1);
+        contract.storage_withdraw(None);
+
+        testing_env!(
+            VMContextBuilder::new()
+                .predecessor_account_id(account("outcome.testnet"))
+                .current_account_id(account("outcome.testnet"))
+                .build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            vec![PromiseResult::Successful(b"[\"1000\"]".to_vec())]
+        );
+        let kept = contract.mt_resolve_transfer(
+            vec![account("alice.testnet")],
+            account("vault.testnet"),
+            vec!["0:yes".to_string()],
+            vec![U128(1_000)],
+            None,
+        );
+
+        assert_eq!(kept, vec![U128(1_000)]);
+        assert_eq!(contract.mt_balance_of(account("vault.testnet"), "0:yes".to_string()), U128(1_000));
+        assert!(get_logs()[0].contains("is not covered by its storage balance"));
+    }
+
+    #[test]
+    fn metadata_describes_outcome() {
+        let contract = funded();
+        let metadata = contract.mt_metadata_token_all(vec!["0:no".to_string(), "bad".to_string()]);
+        let first = metadata[0].as_ref().unwrap();
+        assert_eq!(first.base.id, "0:no");
+        assert_eq!(first.base.decimals.as_deref(), Some("6"));
+        assert!(metadata[1].is_none());
+        assert_eq!(contract.mt_metadata_contract().spec, "mt-1.0.0");
+    }
+}
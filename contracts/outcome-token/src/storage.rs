@@ -0,0 +1,332 @@
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::{assert_one_yocto, env, near, require, AccountId, NearToken, Promise};
+
+use market_types::{MarketId, Outcome};
+
+use crate::{balance_key, OutcomeToken, OutcomeTokenExt};
+
+/// NEP-145 storage account. `used_bytes` covers the registration itself plus
+/// every balance and holder entry the account's tokens occupy.
+#[near(serializers = [borsh])]
+#[derive(Clone, Debug)]
+pub struct StorageAccount {
+    pub deposit: u128,
+    pub used_bytes: u64,
+}
+
+impl StorageAccount {
+    fn available(&self) -> u128 {
+        self.deposit.saturating_sub(storage_cost(self.used_bytes))
+    }
+}
+
+pub(crate) fn storage_cost(bytes: u64) -> u128 {
+    env::storage_byte_cost().as_yoctonear() * bytes as u128
+}
+
+pub(crate) fn refund(account_id: &AccountId, amount: u128) {
+    if amount > 0 {
+        Promise::new(account_id.clone()).transfer(NearToken::from_yoctonear(amount));
+    }
+}
+
+#[near]
+impl StorageManagement for OutcomeToken {
+    /// Register an account or top up its storage balance. Accounts must be
+    /// registered to receive outcome tokens.
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let amount = env::attached_deposit().as_yoctonear();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let registration_only = registration_only.unwrap_or(false);
+
+        let excess = if let Some(account) = self.storage_accounts.get_mut(&account_id) {
+            if registration_only {
+                amount
+            } else {
+                account.deposit += amount;
+                0
+            }
+        } else {
+            let min = storage_cost(self.account_storage_usage);
+            require!(
+                amount >= min,
+                "The attached deposit is less than the minimum storage balance"
+            );
+            let deposit = if registration_only { min } else { amount };
+            self.storage_accounts.insert(
+                account_id.clone(),
+                StorageAccount {
+                    deposit,
+                    used_bytes: self.account_storage_usage,
+                },
+            );
+            amount - deposit
+        };
+        refund(&env::predecessor_account_id(), excess);
+        self.internal_storage_balance_of(&account_id).unwrap()
+    }
+
+    /// Withdraw storage balance not backing any entry.
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<NearToken>) -> StorageBalance {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let account = self
+            .storage_accounts
+            .get_mut(&account_id)
+            .unwrap_or_else(|| env::panic_str(&format!("The account {account_id} is not registered")));
+        let available = account.available();
+        let amount = amount.map_or(available, |amount| amount.as_yoctonear());
+        require!(
+            amount <= available,
+            "The amount is greater than the available storage balance"
+        );
+        account.deposit -= amount;
+        refund(&account_id, amount);
+        self.internal_storage_balance_of(&account_id).unwrap()
+    }
+
+    /// Unregister the caller and refund its deposit. Only possible once every
+    /// balance has been transferred or burned.
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        assert_one_yocto();
+        require!(
+            force != Some(true),
+            "Forced unregistration is not supported"
+        );
+        let account_id = env::predecessor_account_id();
+        let Some(account) = self.storage_accounts.get(&account_id) else {
+            return false;
+        };
+        require!(
+            account.used_bytes <= self.account_storage_usage,
+            "Can't unregister an account that still holds outcome tokens"
+        );
+        let deposit = account.deposit;
+        self.storage_accounts.remove(&account_id);
+        refund(&account_id, deposit);
+        true
+    }
+
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        StorageBalanceBounds {
+            min: NearToken::from_yoctonear(storage_cost(self.account_storage_usage)),
+            max: None,
+        }
+    }
+
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.internal_storage_balance_of(&account_id)
+    }
+}
+
+impl OutcomeToken {
+    /// Bytes one registration occupies, measured with the longest account id.
+    pub(crate) fn measure_account_storage_usage(&mut self) -> u64 {
+        let initial_usage = env::storage_usage();
+        let account_id: AccountId = "a".repeat(64).parse().unwrap();
+        self.storage_accounts.insert(
+            account_id.clone(),
+            StorageAccount {
+                deposit: 0,
+                used_bytes: 0,
+            },
+        );
+        self.storage_accounts.flush();
+        let usage = env::storage_usage() - initial_usage;
+        self.storage_accounts.remove(&account_id);
+        self.storage_accounts.flush();
+        usage
+    }
+
+    /// Bytes a new balance and its holder entry occupy, measured with the
+    /// longest account id.
+    pub(crate) fn measure_balance_storage_usage(&mut self) -> u64 {
+        let initial_usage = self.storage_usage();
+        let account_id: AccountId = "a".repeat(64).parse().unwrap();
+        let key = balance_key(MarketId::MAX, &Outcome::Yes, &account_id);
+        self.balances.insert(key.clone(), u128::MAX);
+        self.add_holder(MarketId::MAX, Outcome::Yes, &account_id);
+        let usage = self.storage_usage() - initial_usage;
+        self.balances.remove(&key);
+        self.remove_holder(MarketId::MAX, Outcome::Yes, &account_id);
+        self.storage_usage();
+        self.holder_count.flush();
+        usage
+    }
+
+    fn internal_storage_balance_of(&self, account_id: &AccountId) -> Option<StorageBalance> {
+        self.storage_accounts.get(account_id).map(|account| StorageBalance {
+            total: NearToken::from_yoctonear(account.deposit),
+            available: NearToken::from_yoctonear(account.available()),
+        })
+    }
+
+    /// Whether crediting `account_id` with tokens of `(market_id, outcome)` is
+    /// paid for: minters hold contract-paid reserves, an existing balance needs
+    /// no new storage, and otherwise the available storage balance must cover a
+    /// new balance and holder entry.
+    pub(crate) fn can_receive(&self, market_id: MarketId, outcome: Outcome, account_id: &AccountId) -> bool {
+        self.minters.contains_key(account_id)
+            || self.internal_balance(market_id, outcome, account_id) > 0
+            || self.storage_accounts.get(account_id).is_some_and(|account| {
+                account.available() >= storage_cost(self.balance_storage_usage)
+            })
+    }
+
+    /// Return the deposit backing entries freed since the account used
+    /// `used_bytes` to `account_id`, instead of leaving it as available balance.
+    pub(crate) fn refund_freed_storage(&mut self, account_id: &AccountId, used_bytes: Option<u64>) {
+        let (Some(used_bytes), Some(account)) = (used_bytes, self.storage_accounts.get_mut(account_id)) else {
+            return;
+        };
+        let freed = storage_cost(used_bytes.saturating_sub(account.used_bytes)).min(account.deposit);
+        account.deposit -= freed;
+        refund(account_id, freed);
+    }
+
+    /// Storage in use, with cached balance and holder writes flushed first.
+    /// Supply and holder counts are per market and left to the contract, so
+    /// their writes stay cached until the end of the call.
+    pub(crate) fn storage_usage(&mut self) -> u64 {
+        self.balances.flush();
+        self.holders.flush();
+        self.holder_index.flush();
+        env::storage_usage()
+    }
+
+    /// Charge the storage added since `initial_usage` to `account_id`, or credit
+    /// back what was freed. Minters are exempt.
+    pub(crate) fn settle_storage(&mut self, account_id: &AccountId, initial_usage: u64) {
+        let usage = self.storage_usage();
+        if self.minters.contains_key(account_id) {
+            return;
+        }
+        let Some(account) = self.storage_accounts.get_mut(account_id) else {
+            require!(
+                usage <= initial_usage,
+                format!("The account {account_id} is not registered, call storage_deposit first")
+            );
+            return;
+        };
+        if usage >= initial_usage {
+            account.used_bytes += usage - initial_usage;
+            require!(
+                storage_cost(account.used_bytes) <= account.deposit,
+                format!("Insufficient storage balance for {account_id}, call storage_deposit first")
+            );
+        } else {
+            account.used_bytes = account
+                .used_bytes
+                .saturating_sub(initial_usage - usage)
+                .max(self.account_storage_usage);
+        }
+    }
+}
+
+#[cfg(test)]
+impl OutcomeToken {
+    /// Register `account_id` with enough deposit for any test's balances.
+    pub(crate) fn register(&mut self, account_id: &AccountId) {
+        self.storage_accounts.insert(
+            account_id.clone(),
+            StorageAccount {
+                deposit: 10u128.pow(24),
+                used_bytes: self.account_storage_usage,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use market_types::Outcome;
+    use near_sdk::json_types::U128;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::testing_env;
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn context(predecessor: &str, deposit: u128) {
+        testing_env!(VMContextBuilder::new()
+            .predecessor_account_id(account(predecessor))
+            .attached_deposit(NearToken::from_yoctonear(deposit))
+            .build());
+    }
+
+    fn setup() -> OutcomeToken {
+        context("market.testnet", 0);
+        OutcomeToken::new(account("owner.testnet"), account("market.testnet"))
+    }
+
+    #[test]
+    #[should_panic(expected = "is not registered, call storage_deposit first")]
+    fn unregistered_accounts_cannot_receive_tokens() {
+        let mut contract = setup();
+        contract.mint(0, Outcome::Yes, account("alice.testnet"), U128(100));
+    }
+
+    #[test]
+    fn balances_are_paid_from_the_storage_deposit() {
+        let mut contract = setup();
+        let min = contract.storage_balance_bounds().min.as_yoctonear();
+        context("alice.testnet", min * 10);
+        contract.storage_deposit(None, None);
+
+        context("market.testnet", 0);
+        contract.mint(0, Outcome::Yes, account("alice.testnet"), U128(100));
+        let used = contract.storage_accounts.get(&account("alice.testnet")).unwrap().used_bytes;
+        assert!(used > contract.account_storage_usage);
+        assert!(used - contract.account_storage_usage <= contract.balance_storage_usage);
+        let available = contract.storage_balance_of(account("alice.testnet")).unwrap().available;
+        assert_eq!(available.as_yoctonear(), min * 10 - storage_cost(used));
+
+        // Burning the balance frees its storage again
+        contract.burn(0, Outcome::Yes, account("alice.testnet"), U128(100));
+        let account_usage = contract.storage_accounts.get(&account("alice.testnet")).unwrap().used_bytes;
+        assert_eq!(account_usage, contract.account_storage_usage);
+    }
+
+    #[test]
+    #[should_panic(expected = "Insufficient storage balance")]
+    fn registration_only_covers_no_balances() {
+        let mut contract = setup();
+        let min = contract.storage_balance_bounds().min.as_yoctonear();
+        context("alice.testnet", min);
+        contract.storage_deposit(None, Some(true));
+
+        context("market.testnet", 0);
+        contract.mint(0, Outcome::Yes, account("alice.testnet"), U128(100));
+    }
+
+    #[test]
+    #[should_panic(expected = "still holds outcome tokens")]
+    fn holders_cannot_unregister() {
+        let mut contract = setup();
+        context("alice.testnet", 10u128.pow(23));
+        contract.storage_deposit(None, None);
+        context("market.testnet", 0);
+        contract.mint(0, Outcome::Yes, account("alice.testnet"), U128(100));
+
+        context("alice.testnet", 1);
+        contract.storage_unregister(None);
+    }
+
+    #[test]
+    fn minters_hold_reserves_without_registering() {
+        let mut contract = setup();
+        contract.mint(0, Outcome::No, account("market.testnet"), U128(100));
+        assert_eq!(contract.balance_of(0, Outcome::No, account("market.testnet")), U128(100));
+    }
+}
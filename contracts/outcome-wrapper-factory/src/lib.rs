@@ -0,0 +1,312 @@
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::store::{LazyOption, LookupMap};
+use near_sdk::{
+    env, near, require, AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseResult,
+};
+
+use market_types::{outcome_token_id, MarketId, Outcome};
+
+const GAS_FOR_WRAPPER_INIT: Gas = Gas::from_tgas(10);
+const GAS_FOR_STORAGE_DEPOSIT: Gas = Gas::from_tgas(10);
+const GAS_FOR_REFRESH_METADATA: Gas = Gas::from_tgas(40);
+const GAS_FOR_CREATE_CALLBACK: Gas = Gas::from_tgas(60);
+
+/// Bytes a new wrapper account uses besides its code: account record and initial state
+const WRAPPER_STATE_BYTES: u128 = 2_000;
+/// Paid to `outcome-token` so the wrapper can hold the position it wraps
+const OUTCOME_TOKEN_STORAGE_DEPOSIT: NearToken = NearToken::from_millinear(50);
+
+/// Deploys one `outcome-wrapper` per `(market_id, outcome)` as a sub-account
+/// `"{market_id}-{yes|no}.{factory}"`, paid for by whoever requests it.
+#[near(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct WrapperFactory {
+    owner: AccountId,
+    outcome_token: AccountId,
+    market_contract: AccountId,
+    /// Wasm deployed to each new wrapper
+    wrapper_code: LazyOption<Vec<u8>>,
+    /// Deployed wrappers: outcome token id -> wrapper account
+    wrappers: LookupMap<String, AccountId>,
+}
+
+#[near]
+impl WrapperFactory {
+    #[init]
+    pub fn new(owner: AccountId, outcome_token: AccountId, market_contract: AccountId) -> Self {
+        Self {
+            owner,
+            outcome_token,
+            market_contract,
+            wrapper_code: LazyOption::new(b"c", None),
+            wrappers: LookupMap::new(b"w"),
+        }
+    }
+
+    /// Store the wrapper wasm, passed as the raw call input. Owner only.
+    /// Wrappers already deployed keep their code.
+    pub fn set_wrapper_code(&mut self) {
+        require!(
+            env::predecessor_account_id() == self.owner,
+            "Only the owner can call this method"
+        );
+        let code = env::input().unwrap_or_default();
+        require!(!code.is_empty(), "Wrapper code is empty");
+        self.wrapper_code.set(Some(code));
+    }
+
+    // ── Create ─────────────────────────────────────────────────────────
+
+    /// Deploy and initialize the wrapper for a position, register it on
+    /// `outcome-token` and load its metadata from the market question. Requires
+    /// at least `get_wrapper_deposit()`; the rest is refunded.
+    #[payable]
+    pub fn create_wrapper(&mut self, market_id: MarketId, outcome: Outcome) -> Promise {
+        require!(
+            !self.wrappers.contains_key(&outcome_token_id(market_id, outcome)),
+            "Wrapper already exists"
+        );
+        let code = self
+            .wrapper_code
+            .get()
+            .clone()
+            .unwrap_or_else(|| env::panic_str("Wrapper code is not set"));
+        let required = self.get_wrapper_deposit().0;
+        let deposit = env::attached_deposit().as_yoctonear();
+        require!(
+            deposit >= required,
+            format!("Attach at least {} yoctoNEAR to create a wrapper", required)
+        );
+
+        Promise::new(Self::wrapper_account_id(market_id, outcome))
+            .create_account()
+            .transfer(NearToken::from_yoctonear(
+                required - OUTCOME_TOKEN_STORAGE_DEPOSIT.as_yoctonear(),
+            ))
+            .deploy_contract(code)
+            .function_call(
+                "new".to_string(),
+                json!({
+                    "outcome_token": self.outcome_token,
+                    "market_contract": self.market_contract,
+                    "market_id": market_id,
+                    "outcome": outcome,
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_WRAPPER_INIT,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_wrapper_created".to_string(),
+                    json!({
+                        "market_id": market_id,
+                        "outcome": outcome,
+                        "creator": env::predecessor_account_id(),
+                        "deposit": U128(deposit),
+                        "refund": U128(deposit - required),
+                    })
+                    .to_string()
+                    .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_CREATE_CALLBACK,
+                ),
+            )
+    }
+
+    /// Record the new wrapper, or refund the whole deposit if deploying it failed.
+    #[private]
+    pub fn on_wrapper_created(
+        &mut self,
+        market_id: MarketId,
+        outcome: Outcome,
+        creator: AccountId,
+        deposit: U128,
+        refund: U128,
+    ) -> bool {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            Promise::new(creator).transfer(NearToken::from_yoctonear(deposit.0));
+            return false;
+        }
+
+        let wrapper_id = Self::wrapper_account_id(market_id, outcome);
+        self.wrappers
+            .insert(outcome_token_id(market_id, outcome), wrapper_id.clone());
+
+        Promise::new(self.outcome_token.clone()).function_call(
+            "storage_deposit".to_string(),
+            json!({ "account_id": wrapper_id }).to_string().into_bytes(),
+            OUTCOME_TOKEN_STORAGE_DEPOSIT,
+            GAS_FOR_STORAGE_DEPOSIT,
+        );
+        Promise::new(wrapper_id).function_call(
+            "refresh_metadata".to_string(),
+            Vec::new(),
+            NearToken::from_yoctonear(0),
+            GAS_FOR_REFRESH_METADATA,
+        );
+        if refund.0 > 0 {
+            Promise::new(creator).transfer(NearToken::from_yoctonear(refund.0));
+        }
+        true
+    }
+
+    // ── Views ──────────────────────────────────────────────────────────
+
+    /// Wrapper account for a position, once it has been created.
+    pub fn get_wrapper(&self, market_id: MarketId, outcome: Outcome) -> Option<AccountId> {
+        self.wrappers
+            .get(&outcome_token_id(market_id, outcome))
+            .cloned()
+    }
+
+    /// Deposit `create_wrapper` needs: storage for the wrapper's code and state
+    /// plus its storage deposit on `outcome-token`.
+    pub fn get_wrapper_deposit(&self) -> U128 {
+        let code_len = self.wrapper_code.get().as_ref().map_or(0, Vec::len) as u128;
+        let storage = env::storage_byte_cost().as_yoctonear() * (code_len + WRAPPER_STATE_BYTES);
+        U128(storage + OUTCOME_TOKEN_STORAGE_DEPOSIT.as_yoctonear())
+    }
+}
+
+impl WrapperFactory {
+    fn wrapper_account_id(market_id: MarketId, outcome: Outcome) -> AccountId {
+        let label = match outcome {
+            Outcome::Yes => "yes",
+            Outcome::No => "no",
+        };
+        format!("{market_id}-{label}.{}", env::current_account_id())
+            .parse()
+            .unwrap_or_else(|_| env::panic_str("Wrapper account id is too long"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::mock::MockAction;
+    use near_sdk::test_utils::{get_created_receipts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    const CODE: &[u8] = b"\0asm wrapper";
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn set_context(predecessor: &str, deposit: u128, promise_results: Vec<PromiseResult>) {
+        let mut context = VMContextBuilder::new()
+            .predecessor_account_id(account(predecessor))
+            .current_account_id(account("factory.testnet"))
+            .attached_deposit(NearToken::from_yoctonear(deposit))
+            .build();
+        context.input = CODE.to_vec();
+        testing_env!(
+            context,
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            promise_results
+        );
+    }
+
+    fn factory_with_code() -> WrapperFactory {
+        set_context("owner.testnet", 0, vec![]);
+        let mut contract = WrapperFactory::new(
+            account("owner.testnet"),
+            account("outcome.testnet"),
+            account("market.testnet"),
+        );
+        contract.set_wrapper_code();
+        contract
+    }
+
+    #[test]
+    fn create_wrapper_deploys_sub_account() {
+        let mut contract = factory_with_code();
+        let required = contract.get_wrapper_deposit().0;
+        set_context("alice.testnet", required, vec![]);
+        let _ = contract.create_wrapper(3, Outcome::Yes);
+
+        let receipt = &get_created_receipts()[0];
+        assert_eq!(receipt.receiver_id, account("3-yes.factory.testnet"));
+        assert!(matches!(receipt.actions[0], MockAction::CreateAccount { .. }));
+        assert!(
+            matches!(&receipt.actions[2], MockAction::DeployContract { code, .. } if code == CODE)
+        );
+        assert!(matches!(
+            &receipt.actions[3],
+            MockAction::FunctionCallWeight { method_name, .. } if method_name == b"new"
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attach at least")]
+    fn create_wrapper_requires_deposit() {
+        let mut contract = factory_with_code();
+        let required = contract.get_wrapper_deposit().0;
+        set_context("alice.testnet", required - 1, vec![]);
+        let _ = contract.create_wrapper(3, Outcome::Yes);
+    }
+
+    #[test]
+    fn created_wrapper_is_recorded_and_registered() {
+        let mut contract = factory_with_code();
+        set_context("factory.testnet", 0, vec![PromiseResult::Successful(vec![])]);
+        assert!(contract.on_wrapper_created(3, Outcome::No, account("alice.testnet"), U128(10), U128(4)));
+
+        assert_eq!(
+            contract.get_wrapper(3, Outcome::No),
+            Some(account("3-no.factory.testnet"))
+        );
+        let receivers: Vec<_> = get_created_receipts()
+            .into_iter()
+            .map(|receipt| receipt.receiver_id)
+            .collect();
+        assert_eq!(
+            receivers,
+            vec![
+                account("outcome.testnet"),
+                account("3-no.factory.testnet"),
+                account("alice.testnet"),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrapper already exists")]
+    fn create_wrapper_rejects_existing_position() {
+        let mut contract = factory_with_code();
+        set_context("factory.testnet", 0, vec![PromiseResult::Successful(vec![])]);
+        contract.on_wrapper_created(3, Outcome::Yes, account("alice.testnet"), U128(10), U128(0));
+
+        let required = contract.get_wrapper_deposit().0;
+        set_context("bob.testnet", required, vec![]);
+        let _ = contract.create_wrapper(3, Outcome::Yes);
+    }
+
+    #[test]
+    fn failed_creation_refunds_deposit() {
+        let mut contract = factory_with_code();
+        set_context("factory.testnet", 0, vec![PromiseResult::Failed]);
+        assert!(!contract.on_wrapper_created(3, Outcome::Yes, account("alice.testnet"), U128(10), U128(4)));
+
+        assert_eq!(contract.get_wrapper(3, Outcome::Yes), None);
+        let receipt = &get_created_receipts()[0];
+        assert_eq!(receipt.receiver_id, account("alice.testnet"));
+        assert!(matches!(
+            receipt.actions[0],
+            MockAction::Transfer { deposit, .. } if deposit == NearToken::from_yoctonear(10)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can call this method")]
+    fn only_owner_sets_code() {
+        let mut contract = factory_with_code();
+        set_context("alice.testnet", 0, vec![]);
+        contract.set_wrapper_code();
+    }
+}
@@ -0,0 +1,501 @@
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::events::{FtBurn, FtMint};
+use near_contract_standards::fungible_token::metadata::{
+    FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC,
+};
+use near_contract_standards::fungible_token::resolver::FungibleTokenResolver;
+use near_contract_standards::fungible_token::FungibleToken;
+use near_contract_standards::storage_management::{
+    StorageBalance, StorageBalanceBounds, StorageManagement,
+};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::LazyOption;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::serde_json::json;
+use near_sdk::{
+    assert_one_yocto, env, near, require, AccountId, BorshStorageKey, Gas, NearToken,
+    PanicOnDefault, Promise, PromiseOrValue, PromiseResult,
+};
+
+use market_types::{outcome_token_id, MarketId, MarketView, Outcome, USDC_DECIMALS};
+
+const GAS_FOR_MT_TRANSFER: Gas = Gas::from_tgas(15);
+const GAS_FOR_UNWRAP_CALLBACK: Gas = Gas::from_tgas(10);
+const GAS_FOR_GET_MARKET: Gas = Gas::from_tgas(10);
+const GAS_FOR_METADATA_CALLBACK: Gas = Gas::from_tgas(10);
+
+/// Longest token name taken from the market question
+const MAX_NAME_LEN: usize = 64;
+
+#[derive(BorshStorageKey, BorshSerialize)]
+#[borsh(crate = "near_sdk::borsh")]
+enum StorageKey {
+    FungibleToken,
+    Metadata,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct WrappedPositionView {
+    pub outcome_token: AccountId,
+    pub market_contract: AccountId,
+    pub market_id: U64,
+    pub outcome: Outcome,
+    pub token_id: String,
+}
+
+/// NEP-141 token backed 1:1 by a single `(market_id, outcome)` position on `outcome-token`.
+/// Positions are wrapped with `mt_transfer_call` and unwrapped with `unwrap`.
+#[near(contract_state)]
+#[derive(PanicOnDefault)]
+pub struct OutcomeWrapper {
+    token: FungibleToken,
+    metadata: LazyOption<FungibleTokenMetadata>,
+    outcome_token: AccountId,
+    market_contract: AccountId,
+    market_id: MarketId,
+    outcome: Outcome,
+}
+
+fn outcome_label(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Yes => "YES",
+        Outcome::No => "NO",
+    }
+}
+
+fn default_metadata(market_id: MarketId, outcome: Outcome) -> FungibleTokenMetadata {
+    FungibleTokenMetadata {
+        spec: FT_METADATA_SPEC.to_string(),
+        name: format!("Nest Market #{market_id} {}", outcome_label(outcome)),
+        symbol: format!("{}-{market_id}", outcome_label(outcome)),
+        icon: None,
+        reference: None,
+        reference_hash: None,
+        decimals: USDC_DECIMALS,
+    }
+}
+
+#[near]
+impl OutcomeWrapper {
+    #[init]
+    pub fn new(
+        outcome_token: AccountId,
+        market_contract: AccountId,
+        market_id: MarketId,
+        outcome: Outcome,
+    ) -> Self {
+        Self {
+            token: FungibleToken::new(StorageKey::FungibleToken),
+            metadata: LazyOption::new(
+                StorageKey::Metadata,
+                Some(&default_metadata(market_id, outcome)),
+            ),
+            outcome_token,
+            market_contract,
+            market_id,
+            outcome,
+        }
+    }
+
+    // ── Wrap ───────────────────────────────────────────────────────────
+
+    /// NEP-245 receiver. Mints wrapped tokens to the previous owner of each
+    /// transferred amount. The owner must be registered on this contract.
+    pub fn mt_on_transfer(
+        &mut self,
+        #[allow(unused_variables)] sender_id: AccountId,
+        previous_owner_ids: Vec<AccountId>,
+        token_ids: Vec<String>,
+        amounts: Vec<U128>,
+        #[allow(unused_variables)] msg: String,
+    ) -> PromiseOrValue<Vec<U128>> {
+        require!(
+            env::predecessor_account_id() == self.outcome_token,
+            "Only the outcome token contract can wrap positions"
+        );
+        require!(
+            previous_owner_ids.len() == token_ids.len() && token_ids.len() == amounts.len(),
+            "Mismatched transfer arguments"
+        );
+
+        let wrapped_id = self.token_id();
+        for ((owner_id, token_id), amount) in
+            previous_owner_ids.iter().zip(&token_ids).zip(&amounts)
+        {
+            require!(
+                *token_id == wrapped_id,
+                "Token id does not match this wrapper"
+            );
+            require!(
+                self.token.accounts.contains_key(owner_id),
+                format!("Account {owner_id} is not registered")
+            );
+            self.token.internal_deposit(owner_id, amount.0);
+            FtMint {
+                owner_id,
+                amount: *amount,
+                memo: Some("wrap"),
+            }
+            .emit();
+        }
+
+        PromiseOrValue::Value(vec![U128(0); amounts.len()])
+    }
+
+    // ── Unwrap ─────────────────────────────────────────────────────────
+
+    /// Burn wrapped tokens and send the underlying position back to the caller.
+    /// Requires exactly 1 yoctoNEAR, which is forwarded to `mt_transfer`.
+    #[payable]
+    pub fn unwrap(&mut self, amount: U128) -> Promise {
+        assert_one_yocto();
+        require!(amount.0 > 0, "Amount must be positive");
+        let account_id = env::predecessor_account_id();
+
+        self.token.internal_withdraw(&account_id, amount.0);
+        FtBurn {
+            owner_id: &account_id,
+            amount,
+            memo: Some("unwrap"),
+        }
+        .emit();
+
+        Promise::new(self.outcome_token.clone())
+            .function_call(
+                "mt_transfer".to_string(),
+                json!({
+                    "receiver_id": account_id,
+                    "token_id": self.token_id(),
+                    "amount": amount,
+                })
+                .to_string()
+                .into_bytes(),
+                NearToken::from_yoctonear(1),
+                GAS_FOR_MT_TRANSFER,
+            )
+            .then(
+                Promise::new(env::current_account_id()).function_call(
+                    "on_unwrap".to_string(),
+                    json!({ "account_id": account_id, "amount": amount })
+                        .to_string()
+                        .into_bytes(),
+                    NearToken::from_yoctonear(0),
+                    GAS_FOR_UNWRAP_CALLBACK,
+                ),
+            )
+    }
+
+    /// Re-mint the wrapped tokens if the position transfer failed.
+    #[private]
+    pub fn on_unwrap(&mut self, account_id: AccountId, amount: U128) -> U128 {
+        require!(
+            env::promise_results_count() == 1,
+            "Expected one promise result"
+        );
+
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => amount,
+            _ => {
+                if !self.token.accounts.contains_key(&account_id) {
+                    self.token.internal_register_account(&account_id);
+                }
+                self.token.internal_deposit(&account_id, amount.0);
+                FtMint {
+                    owner_id: &account_id,
+                    amount,
+                    memo: Some("unwrap refund"),
+                }
+                .emit();
+                U128(0)
+            }
+        }
+    }
+
+    // ── Metadata ───────────────────────────────────────────────────────
+
+    /// Pull the market question from the market contract into the token name.
+    pub fn refresh_metadata(&mut self) -> Promise {
+        Promise::new(self.market_contract.clone())
+            .function_call(
+                "get_market".to_string(),
+                json!({ "market_id": self.market_id })
+                    .to_string()
+                    .into_bytes(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_GET_MARKET,
+            )
+            .then(Promise::new(env::current_account_id()).function_call(
+                "on_market_metadata".to_string(),
+                Vec::new(),
+                NearToken::from_yoctonear(0),
+                GAS_FOR_METADATA_CALLBACK,
+            ))
+    }
+
+    #[private]
+    pub fn on_market_metadata(&mut self) -> FungibleTokenMetadata {
+        require!(
+            env::promise_results_count() == 1,
+            "Expected one promise result"
+        );
+        let market = match env::promise_result(0) {
+            PromiseResult::Successful(bytes) => {
+                near_sdk::serde_json::from_slice::<Option<MarketView>>(&bytes)
+                    .ok()
+                    .flatten()
+            }
+            _ => None,
+        };
+        let market = market.unwrap_or_else(|| env::panic_str("Market not found"));
+
+        let mut metadata = default_metadata(self.market_id, self.outcome);
+        let name = format!("{} — {}", outcome_label(self.outcome), market.question);
+        metadata.name = if name.chars().count() > MAX_NAME_LEN {
+            let truncated: String = name.chars().take(MAX_NAME_LEN - 1).collect();
+            format!("{truncated}…")
+        } else {
+            name
+        };
+        self.metadata.set(&metadata);
+        metadata
+    }
+
+    // ── Views ──────────────────────────────────────────────────────────
+
+    pub fn get_wrapped_position(&self) -> WrappedPositionView {
+        WrappedPositionView {
+            outcome_token: self.outcome_token.clone(),
+            market_contract: self.market_contract.clone(),
+            market_id: U64(self.market_id),
+            outcome: self.outcome,
+            token_id: self.token_id(),
+        }
+    }
+}
+
+impl OutcomeWrapper {
+    fn token_id(&self) -> String {
+        outcome_token_id(self.market_id, self.outcome)
+    }
+}
+
+#[near]
+impl FungibleTokenCore for OutcomeWrapper {
+    #[payable]
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        self.token.ft_transfer(receiver_id, amount, memo)
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        self.token.ft_transfer_call(receiver_id, amount, memo, msg)
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.token.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.token.ft_balance_of(account_id)
+    }
+}
+
+#[near]
+impl FungibleTokenResolver for OutcomeWrapper {
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        let (used, burned) =
+            self.token
+                .internal_ft_resolve_transfer(&sender_id, receiver_id, amount);
+        if burned > 0 {
+            FtBurn {
+                owner_id: &sender_id,
+                amount: burned.into(),
+                memo: Some("Refund burned"),
+            }
+            .emit();
+        }
+        used.into()
+    }
+}
+
+#[near]
+impl FungibleTokenMetadataProvider for OutcomeWrapper {
+    fn ft_metadata(&self) -> FungibleTokenMetadata {
+        self.metadata.get().unwrap()
+    }
+}
+
+#[near]
+impl StorageManagement for OutcomeWrapper {
+    #[payable]
+    fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        self.token.storage_deposit(account_id, registration_only)
+    }
+    #[payable]
+    fn storage_withdraw(&mut self, amount: Option<NearToken>) -> StorageBalance {
+        self.token.storage_withdraw(amount)
+    }
+    #[payable]
+    fn storage_unregister(&mut self, force: Option<bool>) -> bool {
+        // Forced unregistration would burn wrapped supply and strand the backing position
+        require!(
+            force != Some(true),
+            "Forced unregistration is not supported"
+        );
+        self.token.internal_storage_unregister(force).is_some()
+    }
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        self.token.storage_balance_bounds()
+    }
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.token.storage_balance_of(account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn account(id: &str) -> AccountId {
+        id.parse().unwrap()
+    }
+
+    fn set_context(predecessor: &str, deposit: u128, promise_results: Vec<PromiseResult>) {
+        testing_env!(
+            VMContextBuilder::new()
+                .predecessor_account_id(account(predecessor))
+                .current_account_id(account("wrapper.testnet"))
+                .attached_deposit(NearToken::from_yoctonear(deposit))
+                .build(),
+            near_sdk::test_vm_config(),
+            near_sdk::RuntimeFeesConfig::test(),
+            Default::default(),
+            promise_results
+        );
+    }
+
+    fn wrapper_with_alice() -> OutcomeWrapper {
+        set_context("alice.testnet", 0, vec![]);
+        let mut contract = OutcomeWrapper::new(
+            account("outcome.testnet"),
+            account("market.testnet"),
+            3,
+            Outcome::Yes,
+        );
+        contract
+            .token
+            .internal_register_account(&account("alice.testnet"));
+        contract
+    }
+
+    fn wrap(contract: &mut OutcomeWrapper, token_id: &str, amount: u128) {
+        set_context("outcome.testnet", 0, vec![]);
+        contract.mt_on_transfer(
+            account("alice.testnet"),
+            vec![account("alice.testnet")],
+            vec![token_id.to_string()],
+            vec![U128(amount)],
+            String::new(),
+        );
+    }
+
+    #[test]
+    fn wrap_mints_to_previous_owner() {
+        let mut contract = wrapper_with_alice();
+        wrap(&mut contract, "3:yes", 500);
+
+        assert_eq!(contract.ft_balance_of(account("alice.testnet")), U128(500));
+        assert_eq!(contract.ft_total_supply(), U128(500));
+        assert!(get_logs()[0].contains("\"event\":\"ft_mint\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Token id does not match this wrapper")]
+    fn wrap_rejects_other_positions() {
+        let mut contract = wrapper_with_alice();
+        wrap(&mut contract, "3:no", 500);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the outcome token contract can wrap positions")]
+    fn wrap_rejects_unknown_caller() {
+        let mut contract = wrapper_with_alice();
+        set_context("alice.testnet", 0, vec![]);
+        contract.mt_on_transfer(
+            account("alice.testnet"),
+            vec![account("alice.testnet")],
+            vec!["3:yes".to_string()],
+            vec![U128(500)],
+            String::new(),
+        );
+    }
+
+    #[test]
+    fn failed_unwrap_restores_balance() {
+        let mut contract = wrapper_with_alice();
+        wrap(&mut contract, "3:yes", 500);
+
+        set_context("alice.testnet", 1, vec![]);
+        let _ = contract.unwrap(U128(200));
+        assert_eq!(contract.ft_balance_of(account("alice.testnet")), U128(300));
+
+        set_context("wrapper.testnet", 0, vec![PromiseResult::Failed]);
+        assert_eq!(
+            contract.on_unwrap(account("alice.testnet"), U128(200)),
+            U128(0)
+        );
+        assert_eq!(contract.ft_balance_of(account("alice.testnet")), U128(500));
+    }
+
+    #[test]
+    fn metadata_uses_market_question() {
+        let mut contract = wrapper_with_alice();
+        assert_eq!(contract.ft_metadata().symbol, "YES-3");
+
+        let market = json!({
+            "id": "3", "question": "Will it rain tomorrow?", "description": "",
+            "creator": "alice.testnet", "resolution_time_ns": "0", "status": "Open",
+            "outcome": null, "collateral_token": "usdc.testnet",
+            "outcome_token": "outcome.testnet", "oracle": "oracle.testnet", "yes_reserve": "0", "no_reserve": "0", "yes_price": "0",
+            "no_price": "0", "total_lp_shares": "0", "total_collateral": "0",
+            "fee_bps": 200, "dynamic_fee": null, "accrued_fees": "0", "protocol_fees": "0",
+            "creation_bond": "0", "bond_status": "NotRequired", "bond_slash_reason": null,
+            "creator_fee_share_bps": 0,
+            "creator_fees": "0", "access_policy": "Open",
+            "limits": {},
+            "resolution_priority": null,
+            "assertion_id": null, "asserted_outcome": null, "resolver": null, "disputer": null,
+            "assertion_submitted_at_ns": null, "assertion_expires_at_ns": null,
+        });
+        set_context(
+            "wrapper.testnet",
+            0,
+            vec![PromiseResult::Successful(market.to_string().into_bytes())],
+        );
+        contract.on_market_metadata();
+
+        let metadata = contract.ft_metadata();
+        assert_eq!(metadata.name, "YES — Will it rain tomorrow?");
+        assert_eq!(metadata.decimals, 6);
+    }
+}
@@ -0,0 +1,60 @@
+#!/bin/bash
+set -euo pipefail
+
+echo "=== Deploy Outcome Wrapper Factory ==="
+echo ""
+
+NETWORK="${NETWORK:-testnet}"
+FACTORY_ID="${FACTORY_ID:-}"
+OWNER_ID="${OWNER_ID:-$FACTORY_ID}"
+OUTCOME_TOKEN_ID="${OUTCOME_TOKEN_ID:-outcome-token-1.testnet}"
+MARKET_ID="${MARKET_ID:-nest-market-1.testnet}"
+
+if [[ -z "$FACTORY_ID" ]]; then
+  echo "Missing required env var: FACTORY_ID"
+  echo "Example: FACTORY_ID=wrap.nest.testnet ./scripts/deploy-wrapper-factory.sh"
+  exit 1
+fi
+
+echo "Network:       $NETWORK"
+echo "Factory ID:    $FACTORY_ID"
+echo "Owner:         $OWNER_ID"
+echo "Outcome token: $OUTCOME_TOKEN_ID"
+echo "Market:        $MARKET_ID"
+echo ""
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+cd "$ROOT"
+
+echo "Building outcome-wrapper and outcome-wrapper-factory..."
+(cd outcome-wrapper && cargo near build non-reproducible-wasm --no-abi)
+(cd outcome-wrapper-factory && cargo near build non-reproducible-wasm --no-abi)
+
+echo ""
+echo "Deploying factory contract..."
+near deploy "$FACTORY_ID" \
+  target/near/outcome_wrapper_factory/outcome_wrapper_factory.wasm \
+  --network-id "$NETWORK"
+
+echo ""
+echo "Initializing factory contract..."
+near call "$FACTORY_ID" new \
+  "{\"owner\":\"$OWNER_ID\",\"outcome_token\":\"$OUTCOME_TOKEN_ID\",\"market_contract\":\"$MARKET_ID\"}" \
+  --accountId "$FACTORY_ID" \
+  --network-id "$NETWORK" \
+  || echo "Init likely already done; continuing."
+
+echo ""
+echo "Uploading wrapper code..."
+near contract call-function as-transaction "$FACTORY_ID" set_wrapper_code \
+  file-args target/near/outcome_wrapper/outcome_wrapper.wasm \
+  prepaid-gas '300 Tgas' attached-deposit '0 NEAR' \
+  sign-as "$OWNER_ID" network-config "$NETWORK" sign-with-keychain send
+
+echo ""
+echo "Deposit per wrapper:"
+near view "$FACTORY_ID" get_wrapper_deposit '{}' --network-id "$NETWORK"
+
+echo ""
+echo "=== Outcome Wrapper Factory Deployment Complete ==="
+echo "Create: near call $FACTORY_ID create_wrapper '{\"market_id\":0,\"outcome\":\"Yes\"}' --deposit <get_wrapper_deposit> --gas 200000000000000"
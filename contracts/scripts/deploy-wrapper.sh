@@ -0,0 +1,67 @@
+#!/bin/bash
+set -euo pipefail
+
+echo "=== Deploy Outcome Wrapper (NEP-141) ==="
+echo ""
+
+NETWORK="${NETWORK:-testnet}"
+WRAPPER_ID="${WRAPPER_ID:-}"
+SIGNER_ID="${SIGNER_ID:-$WRAPPER_ID}"
+OUTCOME_TOKEN_ID="${OUTCOME_TOKEN_ID:-outcome-token-1.testnet}"
+MARKET_ID="${MARKET_ID:-nest-market-1.testnet}"
+MARKET_NUMBER="${MARKET_NUMBER:-}"
+OUTCOME="${OUTCOME:-Yes}"
+
+if [[ -z "$WRAPPER_ID" || -z "$MARKET_NUMBER" ]]; then
+  echo "Missing required env vars: WRAPPER_ID, MARKET_NUMBER"
+  echo "Example: WRAPPER_ID=m0-yes.nest.testnet MARKET_NUMBER=0 OUTCOME=Yes ./scripts/deploy-wrapper.sh"
+  exit 1
+fi
+
+if [[ "$OUTCOME" != "Yes" && "$OUTCOME" != "No" ]]; then
+  echo "OUTCOME must be Yes or No"
+  exit 1
+fi
+
+echo "Network:       $NETWORK"
+echo "Wrapper ID:    $WRAPPER_ID"
+echo "Outcome token: $OUTCOME_TOKEN_ID"
+echo "Market:        $MARKET_ID"
+echo "Position:      market $MARKET_NUMBER / $OUTCOME"
+echo ""
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+cd "$ROOT"
+
+echo "Building outcome-wrapper..."
+(cd outcome-wrapper && cargo near build non-reproducible-wasm --no-abi)
+
+echo ""
+echo "Deploying wrapper contract..."
+near deploy "$WRAPPER_ID" \
+  target/near/outcome_wrapper/outcome_wrapper.wasm \
+  --network-id "$NETWORK"
+
+echo ""
+echo "Initializing wrapper contract..."
+near call "$WRAPPER_ID" new \
+  "{\"outcome_token\":\"$OUTCOME_TOKEN_ID\",\"market_contract\":\"$MARKET_ID\",\"market_id\":$MARKET_NUMBER,\"outcome\":\"$OUTCOME\"}" \
+  --accountId "$SIGNER_ID" \
+  --network-id "$NETWORK" \
+  || echo "Init likely already done; continuing."
+
+echo ""
+echo "Loading metadata from market question..."
+near call "$WRAPPER_ID" refresh_metadata '{}' \
+  --accountId "$SIGNER_ID" \
+  --gas 50000000000000 \
+  --network-id "$NETWORK"
+
+echo ""
+echo "Wrapper metadata:"
+near view "$WRAPPER_ID" ft_metadata '{}' --network-id "$NETWORK"
+
+echo ""
+echo "=== Outcome Wrapper Deployment Complete ==="
+echo "Wrap:   near call $OUTCOME_TOKEN_ID mt_transfer_call '{\"receiver_id\":\"$WRAPPER_ID\",\"token_id\":\"$MARKET_NUMBER:$(echo "$OUTCOME" | tr '[:upper:]' '[:lower:]')\",\"amount\":\"...\",\"msg\":\"\"}' --depositYocto 1"
+echo "Unwrap: near call $WRAPPER_ID unwrap '{\"amount\":\"...\"}' --depositYocto 1"
@@ -83,7 +83,7 @@ sleep_step
 
 echo ""
 echo "=== Initializing Outcome Token ==="
-near_tx "$OUTCOME_TOKEN_ID" new "{\"market_contract\":\"$MARKET_ID\"}" "$OWNER" "80 Tgas" "0 NEAR"
+near_tx "$OUTCOME_TOKEN_ID" new "{\"owner\":\"$OWNER\",\"market_contract\":\"$MARKET_ID\"}" "$OWNER" "80 Tgas" "0 NEAR"
 sleep_step
 
 echo ""
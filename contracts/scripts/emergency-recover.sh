@@ -2,7 +2,9 @@
 set -euo pipefail
 
 # Emergency recovery helper for nest-markets market contract.
-# Calls owner-only emergency withdrawal methods.
+# Proposes emergency withdrawal admin actions as OWNER_ACCOUNT (an approver).
+# Once other approvers have confirmed them and the timelock has passed, rerun
+# with EXECUTE_ACTION_ID set to execute one.
 
 NETWORK="${NETWORK:-mainnet}"
 OWNER_ACCOUNT="${OWNER_ACCOUNT:-}"
@@ -12,6 +14,10 @@ COLLATERAL_TOKEN="${COLLATERAL_TOKEN:-}"
 
 MARKET_WITHDRAW_TOKEN_AMOUNT="${MARKET_WITHDRAW_TOKEN_AMOUNT:-0}"
 MARKET_WITHDRAW_NEAR_YOCTO="${MARKET_WITHDRAW_NEAR_YOCTO:-0}"
+# Token withdrawals are limited to the surplus above what the contract owes
+# unless forced.
+MARKET_WITHDRAW_FORCE="${MARKET_WITHDRAW_FORCE:-false}"
+EXECUTE_ACTION_ID="${EXECUTE_ACTION_ID:-}"
 
 require_env() {
   local name="$1"
@@ -44,17 +50,25 @@ require_env "OWNER_ACCOUNT" "$OWNER_ACCOUNT"
 require_env "TREASURY_ACCOUNT" "$TREASURY_ACCOUNT"
 require_env "MARKET_ID" "$MARKET_ID"
 
+if [[ -n "$EXECUTE_ACTION_ID" ]]; then
+  near_tx "$MARKET_ID" "execute_action" \
+    "{\"action_id\":\"$EXECUTE_ACTION_ID\"}" \
+    "$OWNER_ACCOUNT" "120 Tgas" "0 NEAR"
+  echo "Market admin action $EXECUTE_ACTION_ID executed."
+  exit 0
+fi
+
 if [[ "$MARKET_WITHDRAW_TOKEN_AMOUNT" != "0" ]]; then
   require_env "COLLATERAL_TOKEN" "$COLLATERAL_TOKEN"
-  near_tx "$MARKET_ID" "emergency_withdraw_token" \
-    "{\"token\":\"$COLLATERAL_TOKEN\",\"receiver_id\":\"$TREASURY_ACCOUNT\",\"amount\":\"$MARKET_WITHDRAW_TOKEN_AMOUNT\"}" \
-    "$OWNER_ACCOUNT" "120 Tgas" "1 yoctoNEAR"
+  near_tx "$MARKET_ID" "propose_action" \
+    "{\"action\":{\"EmergencyWithdrawToken\":{\"token\":\"$COLLATERAL_TOKEN\",\"receiver_id\":\"$TREASURY_ACCOUNT\",\"amount\":\"$MARKET_WITHDRAW_TOKEN_AMOUNT\",\"force\":$MARKET_WITHDRAW_FORCE}}}" \
+    "$OWNER_ACCOUNT" "30 Tgas" "0 NEAR"
 fi
 
 if [[ "$MARKET_WITHDRAW_NEAR_YOCTO" != "0" ]]; then
-  near_tx "$MARKET_ID" "emergency_withdraw_near" \
-    "{\"receiver_id\":\"$TREASURY_ACCOUNT\",\"amount\":\"$MARKET_WITHDRAW_NEAR_YOCTO\"}" \
-    "$OWNER_ACCOUNT" "80 Tgas" "0 NEAR"
+  near_tx "$MARKET_ID" "propose_action" \
+    "{\"action\":{\"EmergencyWithdrawNear\":{\"receiver_id\":\"$TREASURY_ACCOUNT\",\"amount\":\"$MARKET_WITHDRAW_NEAR_YOCTO\"}}}" \
+    "$OWNER_ACCOUNT" "30 Tgas" "0 NEAR"
 fi
 
-echo "Market emergency recovery calls complete."
+echo "Market emergency withdrawals proposed; execute them with EXECUTE_ACTION_ID once the timelock expires."